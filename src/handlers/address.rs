@@ -0,0 +1,238 @@
+use serde_json::json;
+use axum::{extract::Json, response::IntoResponse};
+use base64::{engine::general_purpose, Engine as _};
+use solana_sdk::pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN};
+use crate::handlers::sol::derive_seeded_address;
+use crate::models::request::{
+    AddressSeedInput, AssociatedTokenAddressInput, CreateProgramAddressInput, FindProgramAddressInput, SeedAddressInput,
+};
+use crate::models::response::{
+    AssociatedTokenAddressOutput, AssociatedTokenAddressesOutput, ProgramAddressOutput, SeedAddressOutput,
+};
+use crate::utils::validation::*;
+
+pub fn parse_address_seeds(seeds: &[AddressSeedInput]) -> Result<Vec<Vec<u8>>, String> {
+    if seeds.len() >= MAX_SEEDS {
+        return Err(format!("Too many seeds - at most {} allowed alongside the bump", MAX_SEEDS - 1));
+    }
+    seeds
+        .iter()
+        .map(|seed| {
+            let seed_bytes = match seed {
+                AddressSeedInput::Utf8(text) => text.as_bytes().to_vec(),
+                AddressSeedInput::Pubkey(addr) => validate_pubkey_format(addr, "seed")?.to_bytes().to_vec(),
+                AddressSeedInput::U64(Some(value)) => value.to_le_bytes().to_vec(),
+                AddressSeedInput::U64(None) => return Err("Missing required fields".to_string()),
+                AddressSeedInput::Base64(encoded) => general_purpose::STANDARD
+                    .decode(encoded.trim())
+                    .map_err(|_| "Invalid seed - expected base64".to_string())?,
+            };
+            if seed_bytes.len() > MAX_SEED_LEN {
+                return Err(format!("Invalid seed - at most {} bytes allowed", MAX_SEED_LEN));
+            }
+            Ok(seed_bytes)
+        })
+        .collect()
+}
+
+pub fn find_program_address(input: &FindProgramAddressInput) -> Result<ProgramAddressOutput, String> {
+    let program_address = match input.program_id {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_program = validate_pubkey_format(program_address, "program id")?;
+    let seed_bytes = parse_address_seeds(input.seeds.as_deref().unwrap_or_default())?;
+    let seed_slices: Vec<&[u8]> = seed_bytes.iter().map(Vec::as_slice).collect();
+    let (derived_address, bump) = Pubkey::try_find_program_address(&seed_slices, &parsed_program)
+        .ok_or_else(|| "Unable to find a valid program address for these seeds".to_string())?;
+    Ok(ProgramAddressOutput {
+        address: derived_address.to_string(),
+        program_id: parsed_program.to_string(),
+        bump,
+        on_curve: derived_address.is_on_curve(),
+    })
+}
+
+pub fn create_program_address(input: &CreateProgramAddressInput) -> Result<ProgramAddressOutput, String> {
+    let (program_address, bump) = match (&input.program_id, input.bump) {
+        (Some(addr), Some(bump)) if !addr.trim().is_empty() => (addr.trim(), bump),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_program = validate_pubkey_format(program_address, "program id")?;
+    let seed_bytes = parse_address_seeds(input.seeds.as_deref().unwrap_or_default())?;
+    let bump_seed = [bump];
+    let mut seed_slices: Vec<&[u8]> = seed_bytes.iter().map(Vec::as_slice).collect();
+    seed_slices.push(&bump_seed);
+    let derived_address = Pubkey::create_program_address(&seed_slices, &parsed_program)
+        .map_err(|_| format!("Invalid bump {} - these seeds produce an on-curve address", bump))?;
+    Ok(ProgramAddressOutput {
+        address: derived_address.to_string(),
+        program_id: parsed_program.to_string(),
+        bump,
+        on_curve: derived_address.is_on_curve(),
+    })
+}
+
+pub fn create_seed_address(input: &SeedAddressInput) -> Result<SeedAddressOutput, String> {
+    let base_address = match input.base {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let seed = input.seed.as_deref().ok_or_else(|| "Missing required fields".to_string())?;
+    let parsed_base = validate_pubkey_format(base_address, "base")?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let derived_address = derive_seeded_address(&parsed_base, seed, &parsed_owner)?;
+    Ok(SeedAddressOutput {
+        address: derived_address.to_string(),
+        base: parsed_base.to_string(),
+        seed: seed.to_string(),
+        owner: parsed_owner.to_string(),
+        on_curve: derived_address.is_on_curve(),
+    })
+}
+
+pub fn derive_associated_token_addresses(input: &AssociatedTokenAddressInput) -> Result<AssociatedTokenAddressesOutput, String> {
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let mint_address = match input.mint {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let parsed_mint = validate_pubkey_format(mint_address, "mint")?;
+    let token_programs = match input.token_program {
+        Some(ref program) if !program.trim().is_empty() => vec![validate_token_program(Some(program))?],
+        _ => vec![spl_token::ID, spl_token_2022::ID],
+    };
+    let addresses = token_programs
+        .iter()
+        .map(|token_program| {
+            let (derived_address, bump) = Pubkey::find_program_address(
+                &[parsed_owner.as_ref(), token_program.as_ref(), parsed_mint.as_ref()],
+                &spl_associated_token_account::ID,
+            );
+            AssociatedTokenAddressOutput {
+                token_program: token_program.to_string(),
+                address: derived_address.to_string(),
+                bump,
+                on_curve: derived_address.is_on_curve(),
+            }
+        })
+        .collect();
+    Ok(AssociatedTokenAddressesOutput {
+        owner: parsed_owner.to_string(),
+        mint: parsed_mint.to_string(),
+        addresses,
+    })
+}
+
+pub async fn handle_find_program_address(Json(input): Json<FindProgramAddressInput>) -> impl IntoResponse {
+    match find_program_address(&input) {
+        Ok(derived) => build_success_response(derived),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_create_program_address(Json(input): Json<CreateProgramAddressInput>) -> impl IntoResponse {
+    match create_program_address(&input) {
+        Ok(derived) => build_success_response(derived),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_seed_address(Json(input): Json<SeedAddressInput>) -> impl IntoResponse {
+    match create_seed_address(&input) {
+        Ok(derived) => build_success_response(derived),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_associated_token_address(Json(input): Json<AssociatedTokenAddressInput>) -> impl IntoResponse {
+    match derive_associated_token_addresses(&input) {
+        Ok(derived) => build_success_response(derived),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+
+    fn input<T: DeserializeOwned>(value: serde_json::Value) -> T {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn find_and_create_match_the_runtime_derivation() {
+        let program_id = Pubkey::new_unique();
+        let wallet = Pubkey::new_unique();
+        let seeds = json!([
+            { "type": "utf8", "value": "vault" },
+            { "type": "pubkey", "value": wallet.to_string() },
+            { "type": "u64", "value": "7" },
+        ]);
+        let (expected_address, expected_bump) =
+            Pubkey::find_program_address(&[b"vault", wallet.as_ref(), &7u64.to_le_bytes()], &program_id);
+
+        let found = find_program_address(&input(json!({ "program_id": program_id.to_string(), "seeds": seeds }))).unwrap();
+        assert_eq!(found.address, expected_address.to_string());
+        assert_eq!(found.bump, expected_bump);
+        assert!(!found.on_curve);
+
+        let created = create_program_address(&input(json!({
+            "program_id": program_id.to_string(),
+            "seeds": seeds,
+            "bump": expected_bump,
+        })))
+        .unwrap();
+        assert_eq!(created.address, expected_address.to_string());
+        assert_eq!(created.bump, expected_bump);
+    }
+
+    #[test]
+    fn create_rejects_a_bump_that_lands_on_curve() {
+        let program_id = Pubkey::new_unique();
+        let on_curve_bump = (0..=u8::MAX)
+            .rev()
+            .find(|bump| Pubkey::create_program_address(&[b"vault", &[*bump]], &program_id).is_err())
+            .unwrap();
+        let result = create_program_address(&input(json!({
+            "program_id": program_id.to_string(),
+            "seeds": [{ "type": "utf8", "value": "vault" }],
+            "bump": on_curve_bump,
+        })));
+        assert_eq!(
+            result.err(),
+            Some(format!("Invalid bump {} - these seeds produce an on-curve address", on_curve_bump)),
+        );
+    }
+
+    #[test]
+    fn seed_limits_are_enforced() {
+        let too_many: Vec<AddressSeedInput> =
+            input(json!(vec![json!({ "type": "utf8", "value": "a" }); MAX_SEEDS]));
+        assert!(parse_address_seeds(&too_many).unwrap_err().starts_with("Too many seeds"));
+
+        let too_long: Vec<AddressSeedInput> =
+            input(json!([{ "type": "utf8", "value": "a".repeat(MAX_SEED_LEN + 1) }]));
+        assert!(parse_address_seeds(&too_long).unwrap_err().starts_with("Invalid seed"));
+
+        let missing_bump = create_program_address(&input(json!({ "program_id": Pubkey::new_unique().to_string() })));
+        assert_eq!(missing_bump.err().as_deref(), Some("Missing required fields"));
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::HashSet;
+use serde_json::json;
+use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+use solana_sdk::instruction::Instruction;
+use crate::models::request::ComputeBudgetInput;
+use crate::models::response::{ComputeBudgetedOutput, FeeEstimateOutput, InstructionOutput};
+
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+/// Compute units the runtime grants each non-compute-budget instruction when a
+/// transaction does not set its own limit; the fee estimate assumes the same.
+const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u32 = 200_000;
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+pub fn build_compute_budget_instructions(compute_budget: &ComputeBudgetInput) -> Result<Vec<Instruction>, String> {
+    let mut budget_instructions = Vec::with_capacity(2);
+    if let Some(unit_limit) = compute_budget.compute_unit_limit {
+        if unit_limit == 0 || unit_limit > MAX_COMPUTE_UNIT_LIMIT {
+            return Err(format!(
+                "Invalid compute_unit_limit - must be between 1 and {}",
+                MAX_COMPUTE_UNIT_LIMIT
+            ));
+        }
+        budget_instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(unit_limit));
+    }
+    if let Some(micro_lamports) = compute_budget.priority_fee_micro_lamports {
+        budget_instructions.push(ComputeBudgetInstruction::set_compute_unit_price(micro_lamports));
+    }
+    Ok(budget_instructions)
+}
+
+pub fn prepend_compute_budget(
+    instructions: Vec<Instruction>,
+    compute_budget: &ComputeBudgetInput,
+) -> Result<Vec<Instruction>, String> {
+    let mut budgeted_instructions = build_compute_budget_instructions(compute_budget)?;
+    if instructions.iter().any(|instruction| instruction.program_id == compute_budget::ID)
+        && !budgeted_instructions.is_empty()
+    {
+        return Err("Compute budget is already set by another instruction".to_string());
+    }
+    budgeted_instructions.extend(instructions);
+    Ok(budgeted_instructions)
+}
+
+pub fn parse_compute_budget_instruction(data: &[u8]) -> Option<ComputeBudgetInstruction> {
+    borsh::from_slice(data).ok()
+}
+
+pub fn estimate_fee(instructions: &[Instruction], signature_count: usize) -> FeeEstimateOutput {
+    let mut requested_unit_limit = None;
+    let mut unit_price_micro_lamports = 0u64;
+    let mut program_instruction_count = 0u32;
+    for instruction in instructions {
+        if instruction.program_id != compute_budget::ID {
+            program_instruction_count += 1;
+            continue;
+        }
+        match parse_compute_budget_instruction(&instruction.data) {
+            Some(ComputeBudgetInstruction::SetComputeUnitLimit(units)) => requested_unit_limit = Some(units),
+            Some(ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports)) => unit_price_micro_lamports = micro_lamports,
+            _ => {},
+        }
+    }
+    let compute_unit_limit = requested_unit_limit.unwrap_or_else(|| {
+        program_instruction_count
+            .saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNITS)
+            .min(MAX_COMPUTE_UNIT_LIMIT)
+    });
+    let priority_micro_lamports = compute_unit_limit as u128 * unit_price_micro_lamports as u128;
+    let priority_fee_lamports = priority_micro_lamports.div_ceil(MICRO_LAMPORTS_PER_LAMPORT).min(u64::MAX as u128) as u64;
+    let base_fee_lamports = signature_count as u64 * LAMPORTS_PER_SIGNATURE;
+    FeeEstimateOutput {
+        signatures: signature_count,
+        compute_unit_limit,
+        priority_fee_micro_lamports: unit_price_micro_lamports,
+        base_fee_lamports,
+        priority_fee_lamports,
+        total_lamports: base_fee_lamports.saturating_add(priority_fee_lamports),
+    }
+}
+
+pub fn count_required_signers(instructions: &[Instruction]) -> usize {
+    let signers: HashSet<_> = instructions
+        .iter()
+        .flat_map(|instruction| instruction.accounts.iter())
+        .filter(|account| account.is_signer)
+        .map(|account| account.pubkey)
+        .collect();
+    signers.len().max(1)
+}
+
+/// Wraps an endpoint's output with the instruction list to submit, compute
+/// budget instructions first, and a fee estimate. Endpoint-specific fields are
+/// flattened alongside, so list-only endpoints pass `()`.
+pub fn build_budgeted_response<T: serde::Serialize>(
+    endpoint_output: T,
+    instructions: Vec<Instruction>,
+    compute_budget: &ComputeBudgetInput,
+) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    let budgeted_instructions = match prepend_compute_budget(instructions, compute_budget) {
+        Ok(instructions) => instructions,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let signature_count = count_required_signers(&budgeted_instructions);
+    build_success_response(ComputeBudgetedOutput {
+        output: endpoint_output,
+        instructions: budgeted_instructions.iter().map(InstructionOutput::from).collect(),
+        fee_estimate: estimate_fee(&budgeted_instructions, signature_count),
+    })
+}
+
+fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::system_instruction;
+    use crate::models::response::AssociatedTokenAccountOutput;
+
+    fn budget(compute_unit_limit: Option<u32>, priority_fee_micro_lamports: Option<u64>) -> ComputeBudgetInput {
+        ComputeBudgetInput {
+            compute_unit_limit,
+            priority_fee_micro_lamports,
+        }
+    }
+
+    fn transfer_instruction() -> Instruction {
+        system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1)
+    }
+
+    #[test]
+    fn estimate_fee_uses_default_units_without_budget() {
+        let fee_estimate = estimate_fee(&[transfer_instruction(), transfer_instruction()], 2);
+        assert_eq!(fee_estimate.compute_unit_limit, 2 * DEFAULT_INSTRUCTION_COMPUTE_UNITS);
+        assert_eq!(fee_estimate.base_fee_lamports, 2 * LAMPORTS_PER_SIGNATURE);
+        assert_eq!(fee_estimate.priority_fee_lamports, 0);
+        assert_eq!(fee_estimate.total_lamports, 10_000);
+    }
+
+    #[test]
+    fn estimate_fee_reads_requested_limit_and_price() {
+        let instructions = prepend_compute_budget(vec![transfer_instruction()], &budget(Some(300), Some(2_500))).unwrap();
+        let fee_estimate = estimate_fee(&instructions, 1);
+        assert_eq!(fee_estimate.compute_unit_limit, 300);
+        assert_eq!(fee_estimate.priority_fee_micro_lamports, 2_500);
+        assert_eq!(fee_estimate.priority_fee_lamports, 1);
+        assert_eq!(fee_estimate.total_lamports, 5_001);
+    }
+
+    #[test]
+    fn prepend_compute_budget_rejects_invalid_and_duplicate_budgets() {
+        assert!(prepend_compute_budget(vec![transfer_instruction()], &budget(Some(0), None)).is_err());
+        assert!(prepend_compute_budget(vec![transfer_instruction()], &budget(Some(MAX_COMPUTE_UNIT_LIMIT + 1), None)).is_err());
+        let existing_budget = vec![ComputeBudgetInstruction::set_compute_unit_price(1), transfer_instruction()];
+        assert!(prepend_compute_budget(existing_budget.clone(), &budget(None, Some(5))).is_err());
+        assert_eq!(prepend_compute_budget(existing_budget, &budget(None, None)).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn budgeted_response_prepends_budget_instructions() {
+        let instruction = transfer_instruction();
+        let endpoint_output = || AssociatedTokenAccountOutput {
+            address: instruction.accounts[1].pubkey.to_string(),
+            instruction: InstructionOutput::from(&instruction),
+        };
+        let (_, axum::Json(unbudgeted)) = build_budgeted_response(endpoint_output(), vec![instruction.clone()], &budget(None, None));
+        let (_, axum::Json(budgeted)) = build_budgeted_response(endpoint_output(), vec![instruction.clone()], &budget(Some(1_000), Some(10)));
+        let field_names = |response: &serde_json::Value| {
+            response["data"].as_object().unwrap().keys().cloned().collect::<Vec<String>>()
+        };
+        assert_eq!(field_names(&unbudgeted), field_names(&budgeted));
+        assert_eq!(unbudgeted["data"]["address"], budgeted["data"]["address"]);
+        let program_ids = |response: &serde_json::Value| {
+            response["data"]["instructions"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|instruction| instruction["program_id"].as_str().unwrap().to_string())
+                .collect::<Vec<String>>()
+        };
+        let (compute_budget_program, system_program) = (compute_budget::ID.to_string(), instruction.program_id.to_string());
+        assert_eq!(program_ids(&unbudgeted), [system_program.as_str()]);
+        assert_eq!(
+            program_ids(&budgeted),
+            [compute_budget_program.as_str(), compute_budget_program.as_str(), system_program.as_str()]
+        );
+        assert_eq!(budgeted["data"]["instructions"][2], unbudgeted["data"]["instructions"][0]);
+        assert_eq!(budgeted["data"]["fee_estimate"]["compute_unit_limit"], 1_000);
+        assert_eq!(unbudgeted["data"]["fee_estimate"]["compute_unit_limit"], DEFAULT_INSTRUCTION_COMPUTE_UNITS);
+    }
+
+    #[test]
+    fn budgeted_response_flattens_list_only_output() {
+        let instructions = vec![transfer_instruction(), transfer_instruction()];
+        let (status, axum::Json(response)) = build_budgeted_response((), instructions, &budget(None, Some(10)));
+        assert_eq!(status, axum::http::StatusCode::OK);
+        let field_names: Vec<&String> = response["data"].as_object().unwrap().keys().collect();
+        assert_eq!(field_names, ["fee_estimate", "instructions"]);
+        assert_eq!(response["data"]["instructions"].as_array().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,1303 @@
+use axum::{extract::Json, response::IntoResponse};
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Map, Value};
+use solana_sdk::compute_budget::{self, ComputeBudgetInstruction};
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::nonce::state::{State as NonceState, Versions as NonceVersions};
+use solana_sdk::program_pack::Pack;
+use solana_sdk::program_utils::limited_deserialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use spl_token::state::{Account as LegacyAccount, Mint as LegacyMint, Multisig as LegacyMultisig};
+use spl_token_2022::extension::interest_bearing_mint::instruction::{
+    InitializeInstructionData as InterestBearingInitializeData, InterestBearingMintInstruction,
+};
+use spl_token_2022::extension::interest_bearing_mint::BasisPoints;
+use spl_token_2022::extension::transfer_fee::instruction::TransferFeeInstruction;
+use spl_token_2022::extension::{
+    confidential_transfer, confidential_transfer_fee, cpi_guard, default_account_state, group_member_pointer,
+    group_pointer, interest_bearing_mint, memo_transfer, metadata_pointer, mint_close_authority, permanent_delegate,
+    transfer_fee, transfer_hook, AccountType, BaseState, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use spl_token_2022::instruction::{decode_instruction_data, decode_instruction_type, TokenInstruction};
+use spl_token_2022::state::{Account, AccountState, Mint, Multisig};
+use spl_token_group_interface::state::{TokenGroup, TokenGroupMember};
+use spl_token_metadata_interface::state::TokenMetadata;
+use crate::handlers::compute_budget::parse_compute_budget_instruction;
+use crate::handlers::transaction::{decode_transaction, parse_lookup_tables};
+use crate::models::request::{AccountDecodeInput, InspectAccountInput, InspectionInput};
+use crate::models::response::{
+    AccountDecodeOutput, DecodedExtensionOutput, InspectedAccountOutput, InspectedInstructionOutput, LookupTableResolution, SignatureSlotOutput,
+    TransactionInspectionOutput,
+};
+use crate::utils::validation::*;
+
+const LEGACY_TOKEN_MAX_INSTRUCTION_TAG: u8 = 24;
+
+struct DecodedInstruction {
+    program: &'static str,
+    instruction_type: String,
+    account_names: Vec<&'static str>,
+    fields: Map<String, Value>,
+}
+
+pub fn inspect_instruction(
+    program_id: &Pubkey,
+    accounts: Vec<InspectedAccountOutput>,
+    data: &[u8],
+) -> InspectedInstructionOutput {
+    let decoded = if *program_id == system_program::ID {
+        Some(decode_system_instruction(data))
+    } else if *program_id == spl_token::ID {
+        Some(decode_token_instruction(data, false))
+    } else if *program_id == spl_token_2022::ID {
+        Some(decode_token_instruction(data, true))
+    } else if *program_id == spl_associated_token_account::ID {
+        Some(decode_associated_token_instruction(data))
+    } else if *program_id == spl_memo::ID || *program_id == spl_memo::v1::ID {
+        Some(decode_memo_instruction(data))
+    } else if *program_id == compute_budget::ID {
+        Some(decode_compute_budget_instruction(data))
+    } else {
+        None
+    };
+    let mut inspected = InspectedInstructionOutput {
+        program_id: program_id.to_string(),
+        program: None,
+        instruction_type: None,
+        accounts,
+        fields: Map::new(),
+        instruction_data: general_purpose::STANDARD.encode(data),
+        unknown_program: decoded.is_none(),
+        decode_error: None,
+    };
+    match decoded {
+        Some(Ok(instruction)) => {
+            let default_extra_name = match instruction.program {
+                "spl-token" | "spl-token-2022" => Some("signer"),
+                "memo" => Some("signer"),
+                _ => None,
+            };
+            for (index, account) in inspected.accounts.iter_mut().enumerate() {
+                account.name = instruction.account_names
+                    .get(index)
+                    .copied()
+                    .or(default_extra_name)
+                    .map(str::to_string);
+            }
+            inspected.program = Some(instruction.program.to_string());
+            inspected.instruction_type = Some(instruction.instruction_type);
+            inspected.fields = instruction.fields;
+        },
+        Some(Err((program, error_msg))) => {
+            inspected.program = Some(program.to_string());
+            inspected.decode_error = Some(error_msg);
+        },
+        None => {},
+    }
+    inspected
+}
+
+fn decode_system_instruction(data: &[u8]) -> Result<DecodedInstruction, (&'static str, String)> {
+    let system_instruction = limited_deserialize::<SystemInstruction>(data)
+        .map_err(|_| ("system", "Invalid System Program instruction data".to_string()))?;
+    let (instruction_type, account_names, fields) = match system_instruction {
+        SystemInstruction::CreateAccount { lamports, space, owner } => (
+            "create_account",
+            vec!["funding_account", "new_account"],
+            json!({ "lamports": lamports, "space": space, "owner": owner.to_string() }),
+        ),
+        SystemInstruction::Assign { owner } => (
+            "assign",
+            vec!["account"],
+            json!({ "owner": owner.to_string() }),
+        ),
+        SystemInstruction::Transfer { lamports } => (
+            "transfer",
+            vec!["from", "to"],
+            json!({ "lamports": lamports }),
+        ),
+        SystemInstruction::CreateAccountWithSeed { base, seed, lamports, space, owner } => (
+            "create_account_with_seed",
+            vec!["funding_account", "new_account", "base"],
+            json!({
+                "base": base.to_string(),
+                "seed": seed,
+                "lamports": lamports,
+                "space": space,
+                "owner": owner.to_string(),
+            }),
+        ),
+        SystemInstruction::AdvanceNonceAccount => (
+            "advance_nonce_account",
+            vec!["nonce_account", "recent_blockhashes_sysvar", "nonce_authority"],
+            json!({}),
+        ),
+        SystemInstruction::WithdrawNonceAccount(lamports) => (
+            "withdraw_nonce_account",
+            vec!["nonce_account", "to", "recent_blockhashes_sysvar", "rent_sysvar", "nonce_authority"],
+            json!({ "lamports": lamports }),
+        ),
+        SystemInstruction::InitializeNonceAccount(authority) => (
+            "initialize_nonce_account",
+            vec!["nonce_account", "recent_blockhashes_sysvar", "rent_sysvar"],
+            json!({ "nonce_authority": authority.to_string() }),
+        ),
+        SystemInstruction::AuthorizeNonceAccount(new_authority) => (
+            "authorize_nonce_account",
+            vec!["nonce_account", "nonce_authority"],
+            json!({ "new_authority": new_authority.to_string() }),
+        ),
+        SystemInstruction::Allocate { space } => (
+            "allocate",
+            vec!["account"],
+            json!({ "space": space }),
+        ),
+        SystemInstruction::AllocateWithSeed { base, seed, space, owner } => (
+            "allocate_with_seed",
+            vec!["account", "base"],
+            json!({ "base": base.to_string(), "seed": seed, "space": space, "owner": owner.to_string() }),
+        ),
+        SystemInstruction::AssignWithSeed { base, seed, owner } => (
+            "assign_with_seed",
+            vec!["account", "base"],
+            json!({ "base": base.to_string(), "seed": seed, "owner": owner.to_string() }),
+        ),
+        SystemInstruction::TransferWithSeed { lamports, from_seed, from_owner } => (
+            "transfer_with_seed",
+            vec!["from", "base", "to"],
+            json!({ "lamports": lamports, "from_seed": from_seed, "from_owner": from_owner.to_string() }),
+        ),
+        SystemInstruction::UpgradeNonceAccount => (
+            "upgrade_nonce_account",
+            vec!["nonce_account"],
+            json!({}),
+        ),
+    };
+    Ok(DecodedInstruction {
+        program: "system",
+        instruction_type: instruction_type.to_string(),
+        account_names,
+        fields: into_field_map(fields),
+    })
+}
+
+#[allow(deprecated)]
+fn decode_token_instruction(data: &[u8], is_token_2022: bool) -> Result<DecodedInstruction, (&'static str, String)> {
+    let program = if is_token_2022 { "spl-token-2022" } else { "spl-token" };
+    let instruction_tag = data.first().copied().unwrap_or_default();
+    if !is_token_2022 && instruction_tag > LEGACY_TOKEN_MAX_INSTRUCTION_TAG {
+        return Err((program, format!("Unknown SPL Token instruction {}", instruction_tag)));
+    }
+    let token_instruction = TokenInstruction::unpack(data)
+        .map_err(|_| (program, "Invalid token instruction data".to_string()))?;
+    let (instruction_type, account_names, fields) = match token_instruction {
+        TokenInstruction::InitializeMint { decimals, mint_authority, freeze_authority } => (
+            "initialize_mint",
+            vec!["mint", "rent_sysvar"],
+            json!({
+                "decimals": decimals,
+                "mint_authority": mint_authority.to_string(),
+                "freeze_authority": optional_pubkey(freeze_authority.into()),
+            }),
+        ),
+        TokenInstruction::InitializeMint2 { decimals, mint_authority, freeze_authority } => (
+            "initialize_mint2",
+            vec!["mint"],
+            json!({
+                "decimals": decimals,
+                "mint_authority": mint_authority.to_string(),
+                "freeze_authority": optional_pubkey(freeze_authority.into()),
+            }),
+        ),
+        TokenInstruction::InitializeAccount => (
+            "initialize_account",
+            vec!["account", "mint", "owner", "rent_sysvar"],
+            json!({}),
+        ),
+        TokenInstruction::InitializeAccount2 { owner } => (
+            "initialize_account2",
+            vec!["account", "mint", "rent_sysvar"],
+            json!({ "owner": owner.to_string() }),
+        ),
+        TokenInstruction::InitializeAccount3 { owner } => (
+            "initialize_account3",
+            vec!["account", "mint"],
+            json!({ "owner": owner.to_string() }),
+        ),
+        TokenInstruction::InitializeMultisig { m } => (
+            "initialize_multisig",
+            vec!["multisig", "rent_sysvar"],
+            json!({ "threshold": m }),
+        ),
+        TokenInstruction::InitializeMultisig2 { m } => (
+            "initialize_multisig2",
+            vec!["multisig"],
+            json!({ "threshold": m }),
+        ),
+        TokenInstruction::Transfer { amount } => (
+            "transfer",
+            vec!["source", "destination", "authority"],
+            json!({ "amount": amount }),
+        ),
+        TokenInstruction::TransferChecked { amount, decimals } => (
+            "transfer_checked",
+            vec!["source", "mint", "destination", "authority"],
+            json!({ "amount": amount, "decimals": decimals }),
+        ),
+        TokenInstruction::Approve { amount } => (
+            "approve",
+            vec!["source", "delegate", "owner"],
+            json!({ "amount": amount }),
+        ),
+        TokenInstruction::ApproveChecked { amount, decimals } => (
+            "approve_checked",
+            vec!["source", "mint", "delegate", "owner"],
+            json!({ "amount": amount, "decimals": decimals }),
+        ),
+        TokenInstruction::Revoke => (
+            "revoke",
+            vec!["source", "owner"],
+            json!({}),
+        ),
+        TokenInstruction::SetAuthority { authority_type, new_authority } => (
+            "set_authority",
+            vec!["account", "current_authority"],
+            json!({
+                "authority_type": authority_type_name(&authority_type),
+                "new_authority": optional_pubkey(new_authority.into()),
+            }),
+        ),
+        TokenInstruction::MintTo { amount } => (
+            "mint_to",
+            vec!["mint", "destination", "authority"],
+            json!({ "amount": amount }),
+        ),
+        TokenInstruction::MintToChecked { amount, decimals } => (
+            "mint_to_checked",
+            vec!["mint", "destination", "authority"],
+            json!({ "amount": amount, "decimals": decimals }),
+        ),
+        TokenInstruction::Burn { amount } => (
+            "burn",
+            vec!["account", "mint", "authority"],
+            json!({ "amount": amount }),
+        ),
+        TokenInstruction::BurnChecked { amount, decimals } => (
+            "burn_checked",
+            vec!["account", "mint", "authority"],
+            json!({ "amount": amount, "decimals": decimals }),
+        ),
+        TokenInstruction::CloseAccount => (
+            "close_account",
+            vec!["account", "destination", "owner"],
+            json!({}),
+        ),
+        TokenInstruction::FreezeAccount => (
+            "freeze_account",
+            vec!["account", "mint", "freeze_authority"],
+            json!({}),
+        ),
+        TokenInstruction::ThawAccount => (
+            "thaw_account",
+            vec!["account", "mint", "freeze_authority"],
+            json!({}),
+        ),
+        TokenInstruction::SyncNative => (
+            "sync_native",
+            vec!["account"],
+            json!({}),
+        ),
+        TokenInstruction::GetAccountDataSize { extension_types } => (
+            "get_account_data_size",
+            vec!["mint"],
+            json!({ "extension_types": snake_case_names(&extension_types) }),
+        ),
+        TokenInstruction::InitializeImmutableOwner => (
+            "initialize_immutable_owner",
+            vec!["account"],
+            json!({}),
+        ),
+        TokenInstruction::AmountToUiAmount { amount } => (
+            "amount_to_ui_amount",
+            vec!["mint"],
+            json!({ "amount": amount }),
+        ),
+        TokenInstruction::UiAmountToAmount { ui_amount } => (
+            "ui_amount_to_amount",
+            vec!["mint"],
+            json!({ "ui_amount": ui_amount }),
+        ),
+        TokenInstruction::InitializeMintCloseAuthority { close_authority } => (
+            "initialize_mint_close_authority",
+            vec!["mint"],
+            json!({ "close_authority": optional_pubkey(close_authority.into()) }),
+        ),
+        TokenInstruction::Reallocate { extension_types } => (
+            "reallocate",
+            vec!["account", "payer", "system_program", "owner"],
+            json!({ "extension_types": snake_case_names(&extension_types) }),
+        ),
+        TokenInstruction::CreateNativeMint => (
+            "create_native_mint",
+            vec!["payer", "native_mint", "system_program"],
+            json!({}),
+        ),
+        TokenInstruction::InitializeNonTransferableMint => (
+            "initialize_non_transferable_mint",
+            vec!["mint"],
+            json!({}),
+        ),
+        TokenInstruction::InitializePermanentDelegate { delegate } => (
+            "initialize_permanent_delegate",
+            vec!["mint"],
+            json!({ "delegate": delegate.to_string() }),
+        ),
+        TokenInstruction::WithdrawExcessLamports => (
+            "withdraw_excess_lamports",
+            vec!["source", "destination", "authority"],
+            json!({}),
+        ),
+        TokenInstruction::TransferFeeExtension => decode_transfer_fee_instruction(&data[1..])
+            .map_err(|error_msg| (program, error_msg))?,
+        TokenInstruction::InterestBearingMintExtension => decode_interest_bearing_instruction(&data[1..])
+            .map_err(|error_msg| (program, error_msg))?,
+        TokenInstruction::ConfidentialTransferExtension => ("confidential_transfer_extension", vec![], json!({})),
+        TokenInstruction::DefaultAccountStateExtension => ("default_account_state_extension", vec![], json!({})),
+        TokenInstruction::MemoTransferExtension => ("memo_transfer_extension", vec![], json!({})),
+        TokenInstruction::CpiGuardExtension => ("cpi_guard_extension", vec![], json!({})),
+        TokenInstruction::TransferHookExtension => ("transfer_hook_extension", vec![], json!({})),
+        TokenInstruction::ConfidentialTransferFeeExtension => ("confidential_transfer_fee_extension", vec![], json!({})),
+        TokenInstruction::MetadataPointerExtension => ("metadata_pointer_extension", vec![], json!({})),
+        TokenInstruction::GroupPointerExtension => ("group_pointer_extension", vec![], json!({})),
+        TokenInstruction::GroupMemberPointerExtension => ("group_member_pointer_extension", vec![], json!({})),
+    };
+    Ok(DecodedInstruction {
+        program,
+        instruction_type: instruction_type.to_string(),
+        account_names,
+        fields: into_field_map(fields),
+    })
+}
+
+fn decode_transfer_fee_instruction(data: &[u8]) -> Result<(&'static str, Vec<&'static str>, Value), String> {
+    let transfer_fee_instruction = TransferFeeInstruction::unpack(data)
+        .map_err(|_| "Invalid transfer fee instruction data".to_string())?;
+    let decoded = match transfer_fee_instruction {
+        TransferFeeInstruction::InitializeTransferFeeConfig {
+            transfer_fee_config_authority,
+            withdraw_withheld_authority,
+            transfer_fee_basis_points,
+            maximum_fee,
+        } => (
+            "initialize_transfer_fee_config",
+            vec!["mint"],
+            json!({
+                "transfer_fee_config_authority": optional_pubkey(transfer_fee_config_authority.into()),
+                "withdraw_withheld_authority": optional_pubkey(withdraw_withheld_authority.into()),
+                "transfer_fee_basis_points": transfer_fee_basis_points,
+                "maximum_fee": maximum_fee,
+            }),
+        ),
+        TransferFeeInstruction::TransferCheckedWithFee { amount, decimals, fee } => (
+            "transfer_checked_with_fee",
+            vec!["source", "mint", "destination", "authority"],
+            json!({ "amount": amount, "decimals": decimals, "fee": fee }),
+        ),
+        TransferFeeInstruction::WithdrawWithheldTokensFromMint => (
+            "withdraw_withheld_tokens_from_mint",
+            vec!["mint", "destination", "withdraw_withheld_authority"],
+            json!({}),
+        ),
+        TransferFeeInstruction::WithdrawWithheldTokensFromAccounts { num_token_accounts } => (
+            "withdraw_withheld_tokens_from_accounts",
+            vec!["mint", "destination", "withdraw_withheld_authority"],
+            json!({ "num_token_accounts": num_token_accounts }),
+        ),
+        TransferFeeInstruction::HarvestWithheldTokensToMint => (
+            "harvest_withheld_tokens_to_mint",
+            vec!["mint"],
+            json!({}),
+        ),
+        TransferFeeInstruction::SetTransferFee { transfer_fee_basis_points, maximum_fee } => (
+            "set_transfer_fee",
+            vec!["mint", "transfer_fee_config_authority"],
+            json!({ "transfer_fee_basis_points": transfer_fee_basis_points, "maximum_fee": maximum_fee }),
+        ),
+    };
+    Ok(decoded)
+}
+
+fn decode_interest_bearing_instruction(data: &[u8]) -> Result<(&'static str, Vec<&'static str>, Value), String> {
+    let invalid_data = |_| "Invalid interest bearing mint instruction data".to_string();
+    let decoded = match decode_instruction_type(data).map_err(invalid_data)? {
+        InterestBearingMintInstruction::Initialize => {
+            let initialize_data = decode_instruction_data::<InterestBearingInitializeData>(data).map_err(invalid_data)?;
+            let rate_authority: Option<Pubkey> = initialize_data.rate_authority.into();
+            (
+                "initialize_interest_bearing_mint",
+                vec!["mint"],
+                json!({
+                    "rate_authority": optional_pubkey(rate_authority),
+                    "rate": i16::from(initialize_data.rate),
+                }),
+            )
+        },
+        InterestBearingMintInstruction::UpdateRate => {
+            let new_rate = decode_instruction_data::<BasisPoints>(data).map_err(invalid_data)?;
+            (
+                "update_interest_rate",
+                vec!["mint", "rate_authority"],
+                json!({ "rate": i16::from(*new_rate) }),
+            )
+        },
+    };
+    Ok(decoded)
+}
+
+fn decode_associated_token_instruction(data: &[u8]) -> Result<DecodedInstruction, (&'static str, String)> {
+    let (instruction_type, account_names) = match data.first() {
+        None | Some(0) => (
+            "create",
+            vec!["payer", "associated_token_account", "wallet", "mint", "system_program", "token_program"],
+        ),
+        Some(1) => (
+            "create_idempotent",
+            vec!["payer", "associated_token_account", "wallet", "mint", "system_program", "token_program"],
+        ),
+        Some(2) => (
+            "recover_nested",
+            vec![
+                "nested_account",
+                "nested_mint",
+                "destination",
+                "owner_associated_token_account",
+                "owner_mint",
+                "wallet",
+                "token_program",
+            ],
+        ),
+        Some(tag) => {
+            return Err((
+                "associated-token-account",
+                format!("Unknown associated token account instruction {}", tag),
+            ));
+        },
+    };
+    Ok(DecodedInstruction {
+        program: "associated-token-account",
+        instruction_type: instruction_type.to_string(),
+        account_names,
+        fields: Map::new(),
+    })
+}
+
+fn decode_memo_instruction(data: &[u8]) -> Result<DecodedInstruction, (&'static str, String)> {
+    let memo_text = std::str::from_utf8(data).map_err(|_| ("memo", "Memo is not valid UTF-8".to_string()))?;
+    Ok(DecodedInstruction {
+        program: "memo",
+        instruction_type: "memo".to_string(),
+        account_names: Vec::new(),
+        fields: into_field_map(json!({ "memo": memo_text })),
+    })
+}
+
+fn decode_compute_budget_instruction(data: &[u8]) -> Result<DecodedInstruction, (&'static str, String)> {
+    let budget_instruction = parse_compute_budget_instruction(data)
+        .ok_or_else(|| ("compute-budget", "Invalid compute budget instruction data".to_string()))?;
+    let (instruction_type, fields) = match budget_instruction {
+        ComputeBudgetInstruction::Unused => ("unused", json!({})),
+        ComputeBudgetInstruction::RequestHeapFrame(bytes) => ("request_heap_frame", json!({ "bytes": bytes })),
+        ComputeBudgetInstruction::SetComputeUnitLimit(units) => ("set_compute_unit_limit", json!({ "units": units })),
+        ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports) => {
+            ("set_compute_unit_price", json!({ "micro_lamports": micro_lamports }))
+        },
+        ComputeBudgetInstruction::SetLoadedAccountsDataSizeLimit(bytes) => {
+            ("set_loaded_accounts_data_size_limit", json!({ "bytes": bytes }))
+        },
+    };
+    Ok(DecodedInstruction {
+        program: "compute-budget",
+        instruction_type: instruction_type.to_string(),
+        account_names: Vec::new(),
+        fields: into_field_map(fields),
+    })
+}
+
+fn optional_pubkey(pubkey: Option<Pubkey>) -> Value {
+    pubkey.map(|key| Value::String(key.to_string())).unwrap_or(Value::Null)
+}
+
+fn snake_case_name<T: std::fmt::Debug>(value: &T) -> String {
+    let mut name = String::new();
+    for (index, character) in format!("{:?}", value).chars().enumerate() {
+        if character.is_ascii_uppercase() {
+            if index > 0 {
+                name.push('_');
+            }
+            name.push(character.to_ascii_lowercase());
+        } else {
+            name.push(character);
+        }
+    }
+    name
+}
+
+fn snake_case_names<T: std::fmt::Debug>(values: &[T]) -> Vec<String> {
+    values.iter().map(snake_case_name).collect()
+}
+
+fn into_field_map(fields: Value) -> Map<String, Value> {
+    match fields {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+fn decode_token_account_data(data: &[u8], is_token_2022: bool) -> Result<(&'static str, Value, Option<Vec<DecodedExtensionOutput>>), String> {
+    if !is_token_2022 {
+        let (account_type, fields) = decode_legacy_token_account_data(data)?;
+        return Ok((account_type, fields, None));
+    }
+    if data.len() == Multisig::LEN {
+        let multisig = Multisig::unpack_unchecked(data).map_err(|_| "Invalid multisig account data".to_string())?;
+        return Ok(("multisig", multisig_fields(&multisig), None));
+    }
+    let is_mint = match data.len() {
+        Mint::LEN => true,
+        Account::LEN => false,
+        length if length > Account::LEN => match AccountType::try_from(data[Account::LEN]) {
+            Ok(AccountType::Mint) => true,
+            Ok(AccountType::Account) => false,
+            _ => return Err("Invalid Token-2022 account type".to_string()),
+        },
+        length => return Err(format!("Invalid Token-2022 account data length {}", length)),
+    };
+    if is_mint {
+        let mint = StateWithExtensions::<Mint>::unpack(data).map_err(|_| "Invalid mint account data".to_string())?;
+        Ok(("mint", mint_fields(&mint.base), Some(decode_extensions(&mint)?)))
+    } else {
+        let account = StateWithExtensions::<Account>::unpack(data).map_err(|_| "Invalid token account data".to_string())?;
+        Ok(("account", token_account_fields(&account.base), Some(decode_extensions(&account)?)))
+    }
+}
+
+fn decode_legacy_token_account_data(data: &[u8]) -> Result<(&'static str, Value), String> {
+    match data.len() {
+        LegacyMint::LEN => {
+            let mint = LegacyMint::unpack_unchecked(data).map_err(|_| "Invalid mint account data".to_string())?;
+            Ok(("mint", legacy_mint_fields(&mint)))
+        },
+        LegacyAccount::LEN => {
+            let account = LegacyAccount::unpack_unchecked(data).map_err(|_| "Invalid token account data".to_string())?;
+            Ok(("account", legacy_token_account_fields(&account)))
+        },
+        LegacyMultisig::LEN => {
+            let multisig = LegacyMultisig::unpack_unchecked(data).map_err(|_| "Invalid multisig account data".to_string())?;
+            Ok(("multisig", legacy_multisig_fields(&multisig)))
+        },
+        length => Err(format!("Invalid SPL Token account data length {}", length)),
+    }
+}
+
+fn mint_fields(mint: &Mint) -> Value {
+    json!({
+        "mint_authority": optional_pubkey(mint.mint_authority.into()),
+        "supply": mint.supply,
+        "decimals": mint.decimals,
+        "is_initialized": mint.is_initialized,
+        "freeze_authority": optional_pubkey(mint.freeze_authority.into()),
+    })
+}
+
+fn token_account_fields(account: &Account) -> Value {
+    let rent_exempt_reserve: Option<u64> = account.is_native.into();
+    json!({
+        "mint": account.mint.to_string(),
+        "owner": account.owner.to_string(),
+        "amount": account.amount,
+        "delegate": optional_pubkey(account.delegate.into()),
+        "state": snake_case_name(&account.state),
+        "is_native": rent_exempt_reserve.is_some(),
+        "rent_exempt_reserve": rent_exempt_reserve,
+        "delegated_amount": account.delegated_amount,
+        "close_authority": optional_pubkey(account.close_authority.into()),
+    })
+}
+
+fn multisig_fields(multisig: &Multisig) -> Value {
+    let signers: Vec<String> = multisig.signers
+        .iter()
+        .take(multisig.n as usize)
+        .map(Pubkey::to_string)
+        .collect();
+    json!({
+        "threshold": multisig.m,
+        "signer_count": multisig.n,
+        "is_initialized": multisig.is_initialized,
+        "signers": signers,
+    })
+}
+
+fn legacy_mint_fields(mint: &LegacyMint) -> Value {
+    json!({
+        "mint_authority": optional_pubkey(mint.mint_authority.into()),
+        "supply": mint.supply,
+        "decimals": mint.decimals,
+        "is_initialized": mint.is_initialized,
+        "freeze_authority": optional_pubkey(mint.freeze_authority.into()),
+    })
+}
+
+fn legacy_token_account_fields(account: &LegacyAccount) -> Value {
+    let rent_exempt_reserve: Option<u64> = account.is_native.into();
+    json!({
+        "mint": account.mint.to_string(),
+        "owner": account.owner.to_string(),
+        "amount": account.amount,
+        "delegate": optional_pubkey(account.delegate.into()),
+        "state": snake_case_name(&account.state),
+        "is_native": rent_exempt_reserve.is_some(),
+        "rent_exempt_reserve": rent_exempt_reserve,
+        "delegated_amount": account.delegated_amount,
+        "close_authority": optional_pubkey(account.close_authority.into()),
+    })
+}
+
+fn legacy_multisig_fields(multisig: &LegacyMultisig) -> Value {
+    let signers: Vec<String> = multisig.signers
+        .iter()
+        .take(multisig.n as usize)
+        .map(Pubkey::to_string)
+        .collect();
+    json!({
+        "threshold": multisig.m,
+        "signer_count": multisig.n,
+        "is_initialized": multisig.is_initialized,
+        "signers": signers,
+    })
+}
+
+fn decode_extensions<S: BaseState + Pack>(state: &StateWithExtensions<S>) -> Result<Vec<DecodedExtensionOutput>, String> {
+    let extension_types = state.get_extension_types().map_err(|_| "Invalid Token-2022 extension data".to_string())?;
+    extension_types
+        .iter()
+        .map(|extension_type| {
+            let fields = decode_extension(state, *extension_type)
+                .map_err(|_| format!("Invalid {} extension data", snake_case_name(extension_type)))?;
+            Ok(DecodedExtensionOutput {
+                extension_type: snake_case_name(extension_type),
+                fields: into_field_map(fields),
+            })
+        })
+        .collect()
+}
+
+fn decode_extension<S: BaseState + Pack>(
+    state: &StateWithExtensions<S>,
+    extension_type: ExtensionType,
+) -> Result<Value, solana_sdk::program_error::ProgramError> {
+    let fields = match extension_type {
+        ExtensionType::Uninitialized => json!({}),
+        ExtensionType::TransferFeeConfig => {
+            let config = state.get_extension::<transfer_fee::TransferFeeConfig>()?;
+            let fee_fields = |fee: &transfer_fee::TransferFee| json!({
+                "epoch": u64::from(fee.epoch),
+                "maximum_fee": u64::from(fee.maximum_fee),
+                "transfer_fee_basis_points": u16::from(fee.transfer_fee_basis_points),
+            });
+            json!({
+                "transfer_fee_config_authority": optional_pubkey(config.transfer_fee_config_authority.into()),
+                "withdraw_withheld_authority": optional_pubkey(config.withdraw_withheld_authority.into()),
+                "withheld_amount": u64::from(config.withheld_amount),
+                "older_transfer_fee": fee_fields(&config.older_transfer_fee),
+                "newer_transfer_fee": fee_fields(&config.newer_transfer_fee),
+            })
+        },
+        ExtensionType::TransferFeeAmount => {
+            let amount = state.get_extension::<transfer_fee::TransferFeeAmount>()?;
+            json!({ "withheld_amount": u64::from(amount.withheld_amount) })
+        },
+        ExtensionType::MintCloseAuthority => {
+            let extension = state.get_extension::<mint_close_authority::MintCloseAuthority>()?;
+            json!({ "close_authority": optional_pubkey(extension.close_authority.into()) })
+        },
+        ExtensionType::ConfidentialTransferMint => {
+            let extension = state.get_extension::<confidential_transfer::ConfidentialTransferMint>()?;
+            json!({
+                "authority": optional_pubkey(extension.authority.into()),
+                "auto_approve_new_accounts": bool::from(extension.auto_approve_new_accounts),
+            })
+        },
+        ExtensionType::ConfidentialTransferAccount => {
+            let extension = state.get_extension::<confidential_transfer::ConfidentialTransferAccount>()?;
+            json!({
+                "approved": bool::from(extension.approved),
+                "allow_confidential_credits": bool::from(extension.allow_confidential_credits),
+                "allow_non_confidential_credits": bool::from(extension.allow_non_confidential_credits),
+                "pending_balance_credit_counter": u64::from(extension.pending_balance_credit_counter),
+                "maximum_pending_balance_credit_counter": u64::from(extension.maximum_pending_balance_credit_counter),
+            })
+        },
+        ExtensionType::DefaultAccountState => {
+            let extension = state.get_extension::<default_account_state::DefaultAccountState>()?;
+            let default_state = AccountState::try_from(extension.state)
+                .map(|account_state| snake_case_name(&account_state))
+                .unwrap_or_else(|_| extension.state.to_string());
+            json!({ "state": default_state })
+        },
+        ExtensionType::MemoTransfer => {
+            let extension = state.get_extension::<memo_transfer::MemoTransfer>()?;
+            json!({ "require_incoming_transfer_memos": bool::from(extension.require_incoming_transfer_memos) })
+        },
+        ExtensionType::InterestBearingConfig => {
+            let extension = state.get_extension::<interest_bearing_mint::InterestBearingConfig>()?;
+            json!({
+                "rate_authority": optional_pubkey(extension.rate_authority.into()),
+                "initialization_timestamp": i64::from(extension.initialization_timestamp),
+                "pre_update_average_rate": i16::from(extension.pre_update_average_rate),
+                "last_update_timestamp": i64::from(extension.last_update_timestamp),
+                "current_rate": i16::from(extension.current_rate),
+            })
+        },
+        ExtensionType::CpiGuard => {
+            let extension = state.get_extension::<cpi_guard::CpiGuard>()?;
+            json!({ "lock_cpi": bool::from(extension.lock_cpi) })
+        },
+        ExtensionType::PermanentDelegate => {
+            let extension = state.get_extension::<permanent_delegate::PermanentDelegate>()?;
+            json!({ "delegate": optional_pubkey(extension.delegate.into()) })
+        },
+        ExtensionType::TransferHook => {
+            let extension = state.get_extension::<transfer_hook::TransferHook>()?;
+            json!({
+                "authority": optional_pubkey(extension.authority.into()),
+                "program_id": optional_pubkey(extension.program_id.into()),
+            })
+        },
+        ExtensionType::TransferHookAccount => {
+            let extension = state.get_extension::<transfer_hook::TransferHookAccount>()?;
+            json!({ "transferring": bool::from(extension.transferring) })
+        },
+        ExtensionType::ConfidentialTransferFeeConfig => {
+            let extension = state.get_extension::<confidential_transfer_fee::ConfidentialTransferFeeConfig>()?;
+            json!({
+                "authority": optional_pubkey(extension.authority.into()),
+                "harvest_to_mint_enabled": bool::from(extension.harvest_to_mint_enabled),
+            })
+        },
+        ExtensionType::MetadataPointer => {
+            let extension = state.get_extension::<metadata_pointer::MetadataPointer>()?;
+            json!({
+                "authority": optional_pubkey(extension.authority.into()),
+                "metadata_address": optional_pubkey(extension.metadata_address.into()),
+            })
+        },
+        ExtensionType::TokenMetadata => {
+            let metadata = state.get_variable_len_extension::<TokenMetadata>()?;
+            let additional_metadata: Map<String, Value> = metadata.additional_metadata
+                .into_iter()
+                .map(|(key, value)| (key, Value::String(value)))
+                .collect();
+            json!({
+                "update_authority": optional_pubkey(metadata.update_authority.into()),
+                "mint": metadata.mint.to_string(),
+                "name": metadata.name,
+                "symbol": metadata.symbol,
+                "uri": metadata.uri,
+                "additional_metadata": additional_metadata,
+            })
+        },
+        ExtensionType::GroupPointer => {
+            let extension = state.get_extension::<group_pointer::GroupPointer>()?;
+            json!({
+                "authority": optional_pubkey(extension.authority.into()),
+                "group_address": optional_pubkey(extension.group_address.into()),
+            })
+        },
+        ExtensionType::TokenGroup => {
+            let group = state.get_extension::<TokenGroup>()?;
+            json!({
+                "update_authority": optional_pubkey(group.update_authority.into()),
+                "mint": group.mint.to_string(),
+                "size": u32::from(group.size),
+                "max_size": u32::from(group.max_size),
+            })
+        },
+        ExtensionType::GroupMemberPointer => {
+            let extension = state.get_extension::<group_member_pointer::GroupMemberPointer>()?;
+            json!({
+                "authority": optional_pubkey(extension.authority.into()),
+                "member_address": optional_pubkey(extension.member_address.into()),
+            })
+        },
+        ExtensionType::TokenGroupMember => {
+            let member = state.get_extension::<TokenGroupMember>()?;
+            json!({
+                "mint": member.mint.to_string(),
+                "group": member.group.to_string(),
+                "member_number": u32::from(member.member_number),
+            })
+        },
+        ExtensionType::ConfidentialTransferFeeAmount
+        | ExtensionType::ImmutableOwner
+        | ExtensionType::NonTransferable
+        | ExtensionType::NonTransferableAccount => json!({}),
+    };
+    Ok(fields)
+}
+
+fn decode_nonce_account_data(data: &[u8]) -> Result<Value, String> {
+    if data.len() != NonceState::size() {
+        return Err("Invalid system account data - only nonce accounts can be decoded".to_string());
+    }
+    let versions: NonceVersions = bincode::deserialize(data)
+        .map_err(|_| "Invalid nonce account data".to_string())?;
+    let version = match versions {
+        NonceVersions::Legacy(_) => "legacy",
+        NonceVersions::Current(_) => "current",
+    };
+    let fields = match versions.state() {
+        NonceState::Uninitialized => json!({ "version": version, "state": "uninitialized" }),
+        NonceState::Initialized(nonce_data) => json!({
+            "version": version,
+            "state": "initialized",
+            "authority": nonce_data.authority.to_string(),
+            "nonce": nonce_data.blockhash().to_string(),
+            "lamports_per_signature": nonce_data.fee_calculator.lamports_per_signature,
+        }),
+    };
+    Ok(fields)
+}
+
+fn decode_account_data(input: &AccountDecodeInput) -> Result<AccountDecodeOutput, String> {
+    let (encoded_data, owner_address) = match (&input.data, &input.owner) {
+        (Some(data), Some(owner)) if !owner.trim().is_empty() => (data.trim(), owner.trim()),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let data = general_purpose::STANDARD
+        .decode(encoded_data)
+        .map_err(|_| "Invalid account data - expected base64".to_string())?;
+    let (program, account_type, fields, extensions) = if parsed_owner == system_program::ID {
+        ("system", "nonce", decode_nonce_account_data(&data)?, None)
+    } else if parsed_owner == spl_token::ID || parsed_owner == spl_token_2022::ID {
+        let is_token_2022 = parsed_owner == spl_token_2022::ID;
+        let (account_type, fields, extensions) = decode_token_account_data(&data, is_token_2022)?;
+        let program = if is_token_2022 { "spl-token-2022" } else { "spl-token" };
+        (program, account_type, fields, extensions)
+    } else {
+        return Err(format!("Unsupported account owner {}", parsed_owner));
+    };
+    Ok(AccountDecodeOutput {
+        owner: parsed_owner.to_string(),
+        program: program.to_string(),
+        account_type: account_type.to_string(),
+        fields: into_field_map(fields),
+        extensions,
+    })
+}
+
+fn inspect_serialized_transaction(input: &InspectionInput, encoded_transaction: &str) -> Result<TransactionInspectionOutput, String> {
+    let transaction = decode_transaction(encoded_transaction)?;
+    let lookup_tables = parse_lookup_tables(input.address_lookup_tables.as_deref().unwrap_or_default())?;
+    let message = &transaction.message;
+    let mut account_keys: Vec<String> = message.static_account_keys().iter().map(Pubkey::to_string).collect();
+    let mut lookup_table_keys = Vec::new();
+    if let VersionedMessage::V0(ref v0_message) = message {
+        let mut writable_keys = Vec::new();
+        let mut readonly_keys = Vec::new();
+        for lookup in &v0_message.address_table_lookups {
+            let table = lookup_tables.iter().find(|table| table.key == lookup.account_key);
+            let resolve = |indexes: &[u8]| -> Vec<String> {
+                indexes
+                    .iter()
+                    .map(|index| {
+                        table
+                            .and_then(|table| table.addresses.get(*index as usize))
+                            .map(Pubkey::to_string)
+                            .unwrap_or_else(|| format!("unresolved:{}:{}", lookup.account_key, index))
+                    })
+                    .collect()
+            };
+            let writable = resolve(&lookup.writable_indexes);
+            let readonly = resolve(&lookup.readonly_indexes);
+            writable_keys.extend(writable.iter().cloned());
+            readonly_keys.extend(readonly.iter().cloned());
+            lookup_table_keys.push(LookupTableResolution {
+                table: lookup.account_key.to_string(),
+                writable,
+                readonly,
+            });
+        }
+        account_keys.extend(writable_keys);
+        account_keys.extend(readonly_keys);
+    }
+    let signer_count = message.header().num_required_signatures as usize;
+    let signatures = message.static_account_keys()
+        .iter()
+        .take(signer_count)
+        .zip(transaction.signatures.iter())
+        .map(|(pubkey, signature)| SignatureSlotOutput {
+            pubkey: pubkey.to_string(),
+            signature: (*signature != Signature::default()).then(|| signature.to_string()),
+        })
+        .collect();
+    let instructions = message.instructions()
+        .iter()
+        .map(|compiled_instruction| {
+            let program_id = message.static_account_keys()[compiled_instruction.program_id_index as usize];
+            let accounts = compiled_instruction.accounts
+                .iter()
+                .map(|index| {
+                    let index = *index as usize;
+                    InspectedAccountOutput {
+                        pubkey: account_keys.get(index).cloned().unwrap_or_default(),
+                        name: None,
+                        is_signer: message.is_signer(index),
+                        is_writable: message.is_maybe_writable(index),
+                    }
+                })
+                .collect();
+            inspect_instruction(&program_id, accounts, &compiled_instruction.data)
+        })
+        .collect();
+    Ok(TransactionInspectionOutput {
+        version: match message {
+            VersionedMessage::Legacy(_) => "legacy".to_string(),
+            VersionedMessage::V0(_) => "v0".to_string(),
+        },
+        fee_payer: account_keys[0].clone(),
+        recent_blockhash: message.recent_blockhash().to_string(),
+        signatures,
+        account_keys,
+        lookup_table_keys,
+        instructions,
+    })
+}
+
+fn inspect_instruction_triple(input: &InspectionInput) -> Result<InspectedInstructionOutput, String> {
+    let program_address = match input.program_id {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_program = validate_pubkey_format(program_address, "program id")?;
+    let instruction_data = general_purpose::STANDARD
+        .decode(input.instruction_data.as_deref().unwrap_or_default().trim())
+        .map_err(|_| "Invalid instruction data - expected base64".to_string())?;
+    let accounts = input.accounts
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|account| {
+            let (pubkey, is_signer, is_writable) = match account {
+                InspectAccountInput::Address(pubkey) => (pubkey, false, false),
+                InspectAccountInput::Meta { pubkey, is_signer, is_writable } => (pubkey, *is_signer, *is_writable),
+            };
+            Ok(InspectedAccountOutput {
+                pubkey: validate_pubkey_format(pubkey, "account")?.to_string(),
+                name: None,
+                is_signer,
+                is_writable,
+            })
+        })
+        .collect::<Result<Vec<InspectedAccountOutput>, String>>()?;
+    Ok(inspect_instruction(&parsed_program, accounts, &instruction_data))
+}
+
+pub async fn handle_inspection(Json(input): Json<InspectionInput>) -> impl IntoResponse {
+    match input.transaction {
+        Some(ref encoded) if !encoded.trim().is_empty() => match inspect_serialized_transaction(&input, encoded) {
+            Ok(inspection) => build_success_response(inspection),
+            Err(error_msg) => build_error_response(&error_msg),
+        },
+        _ => match inspect_instruction_triple(&input) {
+            Ok(inspection) => build_success_response(inspection),
+            Err(error_msg) => build_error_response(&error_msg),
+        },
+    }
+}
+
+pub async fn handle_account_decoding(Json(input): Json<AccountDecodeInput>) -> impl IntoResponse {
+    match decode_account_data(&input) {
+        Ok(decoded) => build_success_response(decoded),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::Instruction;
+    use solana_sdk::message::v0;
+    use solana_sdk::system_instruction;
+    use solana_sdk::transaction::VersionedTransaction;
+    use spl_token_2022::extension::{BaseStateWithExtensionsMut, StateWithExtensionsMut};
+
+    fn inspect(instruction: &Instruction) -> InspectedInstructionOutput {
+        let accounts = instruction.accounts
+            .iter()
+            .map(|account| InspectedAccountOutput {
+                pubkey: account.pubkey.to_string(),
+                name: None,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect();
+        inspect_instruction(&instruction.program_id, accounts, &instruction.data)
+    }
+
+    fn account_names(inspected: &InspectedInstructionOutput) -> Vec<Option<&str>> {
+        inspected.accounts.iter().map(|account| account.name.as_deref()).collect()
+    }
+
+    #[test]
+    fn decodes_system_transfer() {
+        let inspected = inspect(&system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 42));
+        assert_eq!(inspected.program.as_deref(), Some("system"));
+        assert_eq!(inspected.instruction_type.as_deref(), Some("transfer"));
+        assert_eq!(inspected.fields["lamports"], 42);
+        assert_eq!(account_names(&inspected), [Some("from"), Some("to")]);
+        assert!(!inspected.unknown_program);
+    }
+
+    #[test]
+    fn decodes_token_transfer_checked_with_multisig_signers() {
+        let multisig_signer = Pubkey::new_unique();
+        let instruction = spl_token::instruction::transfer_checked(
+            &spl_token::ID,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &[&multisig_signer],
+            1_500,
+            6,
+        )
+        .unwrap();
+        let inspected = inspect(&instruction);
+        assert_eq!(inspected.program.as_deref(), Some("spl-token"));
+        assert_eq!(inspected.instruction_type.as_deref(), Some("transfer_checked"));
+        assert_eq!(inspected.fields["amount"], 1_500);
+        assert_eq!(inspected.fields["decimals"], 6);
+        assert_eq!(
+            account_names(&inspected),
+            [Some("source"), Some("mint"), Some("destination"), Some("authority"), Some("signer")]
+        );
+    }
+
+    #[test]
+    fn decodes_token_2022_transfer_fee_extension() {
+        let instruction = spl_token_2022::extension::transfer_fee::instruction::transfer_checked_with_fee(
+            &spl_token_2022::ID,
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &[],
+            1_000,
+            2,
+            10,
+        )
+        .unwrap();
+        let inspected = inspect(&instruction);
+        assert_eq!(inspected.program.as_deref(), Some("spl-token-2022"));
+        assert_eq!(inspected.instruction_type.as_deref(), Some("transfer_checked_with_fee"));
+        assert_eq!(inspected.fields["fee"], 10);
+    }
+
+    #[test]
+    fn legacy_token_program_rejects_token_2022_instructions() {
+        let mut instruction = spl_token_2022::instruction::initialize_mint_close_authority(
+            &spl_token_2022::ID,
+            &Pubkey::new_unique(),
+            None,
+        )
+        .unwrap();
+        instruction.program_id = spl_token::ID;
+        let inspected = inspect(&instruction);
+        assert_eq!(inspected.program.as_deref(), Some("spl-token"));
+        assert!(inspected.decode_error.is_some());
+        assert!(inspected.instruction_type.is_none());
+    }
+
+    #[test]
+    fn decodes_compute_budget_and_rejects_trailing_bytes() {
+        let inspected = inspect(&ComputeBudgetInstruction::set_compute_unit_price(7_500));
+        assert_eq!(inspected.program.as_deref(), Some("compute-budget"));
+        assert_eq!(inspected.instruction_type.as_deref(), Some("set_compute_unit_price"));
+        assert_eq!(inspected.fields["micro_lamports"], 7_500);
+
+        let inspected = inspect(&ComputeBudgetInstruction::set_compute_unit_limit(300_000));
+        assert_eq!(inspected.instruction_type.as_deref(), Some("set_compute_unit_limit"));
+        assert_eq!(inspected.fields["units"], 300_000);
+
+        let mut padded_instruction = ComputeBudgetInstruction::set_compute_unit_limit(1);
+        padded_instruction.data.push(0);
+        assert!(inspect(&padded_instruction).decode_error.is_some());
+    }
+
+    #[test]
+    fn decodes_memo_and_flags_unknown_programs() {
+        let inspected = inspect(&spl_memo::build_memo(b"order 42", &[]));
+        assert_eq!(inspected.program.as_deref(), Some("memo"));
+        assert_eq!(inspected.fields["memo"], "order 42");
+
+        let unknown_instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[1, 2, 3], vec![]);
+        let inspected = inspect(&unknown_instruction);
+        assert!(inspected.unknown_program);
+        assert!(inspected.program.is_none());
+        assert_eq!(inspected.instruction_data, "AQID");
+    }
+
+    #[test]
+    fn inspects_v0_transaction_with_unresolved_lookup() {
+        let fee_payer = Pubkey::new_unique();
+        let table_recipient = Pubkey::new_unique();
+        let lookup_table = solana_sdk::address_lookup_table::AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: vec![table_recipient],
+        };
+        let message = v0::Message::try_compile(
+            &fee_payer,
+            &[system_instruction::transfer(&fee_payer, &table_recipient, 9)],
+            std::slice::from_ref(&lookup_table),
+            Hash::new_unique(),
+        )
+        .unwrap();
+        let transaction = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(message),
+        };
+        let encoded_transaction = general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap());
+        let input = InspectionInput {
+            transaction: Some(encoded_transaction.clone()),
+            address_lookup_tables: None,
+            program_id: None,
+            accounts: None,
+            instruction_data: None,
+        };
+        let inspection = inspect_serialized_transaction(&input, &encoded_transaction).unwrap();
+        assert_eq!(inspection.version, "v0");
+        assert_eq!(inspection.fee_payer, fee_payer.to_string());
+        assert_eq!(inspection.signatures[0].signature, None);
+        let unresolved_key = format!("unresolved:{}:0", lookup_table.key);
+        assert_eq!(inspection.instructions[0].accounts[1].pubkey, unresolved_key);
+        assert_eq!(inspection.instructions[0].fields["lamports"], 9);
+    }
+
+    fn decode(data: &[u8], owner: &Pubkey) -> Result<AccountDecodeOutput, String> {
+        decode_account_data(&AccountDecodeInput {
+            data: Some(general_purpose::STANDARD.encode(data)),
+            owner: Some(owner.to_string()),
+        })
+    }
+
+    #[test]
+    fn decodes_legacy_spl_token_mint_and_account() {
+        let mint_authority = Pubkey::new_unique();
+        let mut mint_data = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: Some(mint_authority).into(),
+            supply: 1_000,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        }
+        .pack_into_slice(&mut mint_data);
+        let decoded = decode(&mint_data, &spl_token::ID).unwrap();
+        assert_eq!((decoded.program.as_str(), decoded.account_type.as_str()), ("spl-token", "mint"));
+        assert_eq!(decoded.fields["mint_authority"], mint_authority.to_string());
+        assert_eq!(decoded.fields["supply"], 1_000);
+        assert!(decoded.fields["freeze_authority"].is_null());
+        assert!(decoded.extensions.is_none());
+
+        let owner = Pubkey::new_unique();
+        let mut account_data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint: Pubkey::new_unique(),
+            owner,
+            amount: 5,
+            state: spl_token::state::AccountState::Frozen,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut account_data);
+        let decoded = decode(&account_data, &spl_token::ID).unwrap();
+        assert_eq!(decoded.account_type, "account");
+        assert_eq!(decoded.fields["owner"], owner.to_string());
+        assert_eq!(decoded.fields["state"], "frozen");
+    }
+
+    #[test]
+    fn legacy_and_token_2022_base_layouts_decode_to_the_same_fields() {
+        let mut multisig_data = vec![0u8; LegacyMultisig::LEN];
+        LegacyMultisig {
+            m: 1,
+            n: 2,
+            is_initialized: true,
+            signers: [Pubkey::new_unique(); spl_token::instruction::MAX_SIGNERS],
+        }
+        .pack_into_slice(&mut multisig_data);
+        let mut account_data = vec![0u8; LegacyAccount::LEN];
+        LegacyAccount {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            amount: 42,
+            delegate: Some(Pubkey::new_unique()).into(),
+            state: spl_token::state::AccountState::Initialized,
+            is_native: Some(2_039_280).into(),
+            delegated_amount: 7,
+            close_authority: None.into(),
+        }
+        .pack_into_slice(&mut account_data);
+
+        for (data, account_type) in [(multisig_data, "multisig"), (account_data, "account")] {
+            let legacy = decode(&data, &spl_token::ID).unwrap();
+            let token_2022 = decode(&data, &spl_token_2022::ID).unwrap();
+            assert_eq!(legacy.account_type, account_type);
+            assert_eq!(token_2022.account_type, account_type);
+            assert_eq!(legacy.fields, token_2022.fields);
+        }
+    }
+
+    #[test]
+    fn decodes_token_2022_mint_extensions() {
+        let close_authority = Pubkey::new_unique();
+        let space = ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MintCloseAuthority]).unwrap();
+        let mut mint_data = vec![0u8; space];
+        let mut mint_state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut mint_data).unwrap();
+        mint_state.init_extension::<mint_close_authority::MintCloseAuthority>(true).unwrap().close_authority =
+            Some(close_authority).try_into().unwrap();
+        mint_state.base = Mint {
+            decimals: 2,
+            is_initialized: true,
+            ..Default::default()
+        };
+        mint_state.pack_base();
+        mint_state.init_account_type().unwrap();
+
+        let decoded = decode(&mint_data, &spl_token_2022::ID).unwrap();
+        assert_eq!((decoded.program.as_str(), decoded.account_type.as_str()), ("spl-token-2022", "mint"));
+        assert_eq!(decoded.fields["decimals"], 2);
+        let extensions = decoded.extensions.unwrap();
+        assert_eq!(extensions.len(), 1);
+        assert_eq!(extensions[0].fields["close_authority"], close_authority.to_string());
+        assert!(decode(&mint_data, &spl_token::ID).is_err());
+    }
+
+    #[test]
+    fn rejects_unsupported_owner() {
+        assert!(decode(&[0u8; 82], &Pubkey::new_unique()).is_err());
+    }
+}
@@ -1,22 +1,255 @@
-use axum::{response::IntoResponse};
-use crate::models::response::KeypairOutput;
-use base64::{engine::general_purpose, Engine as _};
+use axum::body::Bytes;
+use axum::{extract::{Json, State}, response::IntoResponse};
+use crate::models::request::{KeypairGenerationInput, KeypairRecoveryInput, MnemonicGenerationInput};
+use crate::models::response::{DerivedKeypairOutput, KeypairOutput, MnemonicKeypairOutput};
+use crate::services::keystore::run_blocking;
+use crate::state::AppState;
+use crate::utils::crypto::{derive_keypair, generate_mnemonic, seed_from_mnemonic};
 use bs58;
-use solana_sdk::signature::Keypair;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use solana_sdk::signature::{Keypair, Signer};
 
-pub async fn handle_keypair_generation() -> impl IntoResponse {
+const DEFAULT_DERIVATION_PATH: &str = "m/44'/501'/0'/0'";
+const DEFAULT_DERIVATION_TEMPLATE: &str = "m/44'/501'/{index}'/0'";
+const INDEX_PLACEHOLDER: &str = "{index}";
+const MAX_DERIVED_ACCOUNTS: u32 = 100;
+
+pub async fn handle_keypair_generation(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> impl IntoResponse {
+    let input: KeypairGenerationInput = match parse_optional_body(&body) {
+        Ok(input) => input,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let persist_keypair = input.persist.unwrap_or(false);
     let new_keypair = Keypair::new();
-    let encoded_secret = bs58::encode(&new_keypair.to_bytes()).into_string();
     let encoded_pubkey = new_keypair.pubkey().to_string();
+    if persist_keypair {
+        let keystore = match state.keystore {
+            Some(ref keystore) => keystore.clone(),
+            None => return build_error_response("Keystore is not configured"),
+        };
+        let stored_entry = match run_blocking(keystore, move |keystore| keystore.store(&new_keypair)).await {
+            Ok(entry) => entry,
+            Err(error) => return build_error_response(&error.to_string()),
+        };
+        return build_success_response(KeypairOutput {
+            pubkey: encoded_pubkey,
+            secret: None,
+            key_id: Some(stored_entry.key_id),
+        });
+    }
+    let encoded_secret = bs58::encode(&new_keypair.to_bytes()).into_string();
     let keypair_result = KeypairOutput {
         pubkey: encoded_pubkey,
-        secret: encoded_secret,
-    };
-    (
-        axum::http::StatusCode::OK,
-        axum::Json(serde_json::json!({
-            "success": true,
-            "data": keypair_result
-        }))
-    )
-}
\ No newline at end of file
+        secret: Some(encoded_secret),
+        key_id: None,
+    };
+    build_success_response(keypair_result)
+}
+
+pub async fn handle_mnemonic_generation(body: Bytes) -> impl IntoResponse {
+    let input: MnemonicGenerationInput = match parse_optional_body(&body) {
+        Ok(input) => input,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let mnemonic_phrase = match generate_mnemonic(input.word_count.unwrap_or(12)) {
+        Ok(phrase) => phrase,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let derived_accounts = match derive_accounts(
+        &mnemonic_phrase,
+        input.passphrase.as_deref().unwrap_or(""),
+        input.derivation_path.as_deref(),
+        input.start_index,
+        input.count,
+    ) {
+        Ok(accounts) => accounts,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    build_success_response(MnemonicKeypairOutput {
+        mnemonic: mnemonic_phrase,
+        accounts: derived_accounts,
+    })
+}
+
+pub async fn handle_keypair_recovery(Json(input): Json<KeypairRecoveryInput>) -> impl IntoResponse {
+    let mnemonic_phrase = match input.mnemonic {
+        Some(ref phrase) if !phrase.trim().is_empty() => phrase.trim(),
+        _ => return build_error_response("Missing required fields"),
+    };
+    let mut derived_accounts = match derive_accounts(
+        mnemonic_phrase,
+        input.passphrase.as_deref().unwrap_or(""),
+        input.derivation_path.as_deref(),
+        input.start_index,
+        input.count,
+    ) {
+        Ok(accounts) => accounts,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    if input.count.is_some() {
+        return build_success_response(derived_accounts);
+    }
+    build_success_response(derived_accounts.remove(0))
+}
+
+/// Parses an optional JSON body: an empty body means "all defaults", but a body
+/// that is present and fails to parse is rejected rather than treated as absent.
+fn parse_optional_body<T: DeserializeOwned + Default>(body: &[u8]) -> Result<T, String> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(T::default());
+    }
+    serde_json::from_slice(body).map_err(|error| format!("Invalid request body: {}", error))
+}
+
+fn derive_accounts(
+    mnemonic_phrase: &str,
+    passphrase: &str,
+    derivation_path: Option<&str>,
+    start_index: Option<u32>,
+    count: Option<u32>,
+) -> Result<Vec<DerivedKeypairOutput>, String> {
+    let derivation_paths = expand_derivation_paths(derivation_path, start_index, count)?;
+    let seed = seed_from_mnemonic(mnemonic_phrase, passphrase)?;
+    derivation_paths
+        .into_iter()
+        .map(|path| {
+            let derived_keypair = derive_keypair(&seed, &path)?;
+            Ok(DerivedKeypairOutput {
+                derivation_path: path,
+                keypair: KeypairOutput {
+                    pubkey: derived_keypair.pubkey().to_string(),
+                    secret: Some(bs58::encode(&derived_keypair.to_bytes()).into_string()),
+                    key_id: None,
+                },
+            })
+        })
+        .collect()
+}
+
+fn expand_derivation_paths(
+    derivation_path: Option<&str>,
+    start_index: Option<u32>,
+    count: Option<u32>,
+) -> Result<Vec<String>, String> {
+    let start_index = start_index.unwrap_or(0);
+    let path_template = match derivation_path {
+        Some(path) if !path.trim().is_empty() => path.trim(),
+        _ if count.is_some() => DEFAULT_DERIVATION_TEMPLATE,
+        _ => DEFAULT_DERIVATION_PATH,
+    };
+    let account_count = match count {
+        Some(n) if n == 0 || n > MAX_DERIVED_ACCOUNTS => {
+            return Err(format!("Invalid count - must be between 1 and {}", MAX_DERIVED_ACCOUNTS));
+        },
+        Some(n) => n,
+        None => 1,
+    };
+    if count.is_some() && !path_template.contains(INDEX_PLACEHOLDER) {
+        return Err(format!("Derivation path must contain {} in range mode", INDEX_PLACEHOLDER));
+    }
+    (0..account_count)
+        .map(|offset| {
+            let index = start_index
+                .checked_add(offset)
+                .ok_or_else(|| "Invalid start_index - range overflows".to_string())?;
+            Ok(path_template.replace(INDEX_PLACEHOLDER, &index.to_string()))
+        })
+        .collect()
+}
+
+fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use axum::http::StatusCode;
+    use crate::services::keystore::Keystore;
+
+    async fn response_json(response: impl IntoResponse) -> (StatusCode, serde_json::Value) {
+        let response = response.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn missing_body_returns_plaintext_secret() {
+        let (status, body) = response_json(handle_keypair_generation(State(AppState::default()), Bytes::new()).await).await;
+        assert_eq!(status, StatusCode::OK);
+        let secret = body["data"]["secret"].as_str().unwrap();
+        let keypair = Keypair::from_bytes(&bs58::decode(secret).into_vec().unwrap()).unwrap();
+        assert_eq!(body["data"]["pubkey"], keypair.pubkey().to_string());
+        assert!(body["data"].get("key_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn unparseable_body_is_rejected() {
+        for body in [&br#"{"persist": "true"}"#[..], b"{persist: true", b"null"] {
+            let (status, response) =
+                response_json(handle_keypair_generation(State(AppState::default()), Bytes::from_static(body)).await).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert!(response["error"].as_str().unwrap().starts_with("Invalid request body"), "{}", response);
+            assert!(response.get("data").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn persist_requires_keystore() {
+        let body = Bytes::from_static(br#"{"persist": true}"#);
+        let (status, response) = response_json(handle_keypair_generation(State(AppState::default()), body).await).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "Keystore is not configured");
+    }
+
+    #[tokio::test]
+    async fn persisted_keypair_is_stored_without_secret() {
+        let directory = tempfile::tempdir().unwrap();
+        let keystore = Arc::new(Keystore::open(directory.path(), "passphrase").unwrap());
+        let state = AppState {
+            keystore: Some(keystore.clone()),
+            ..AppState::default()
+        };
+        let body = Bytes::from_static(br#"{"persist": true}"#);
+        let (status, response) = response_json(handle_keypair_generation(State(state), body).await).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(response["data"].get("secret").is_none());
+        let key_id = response["data"]["key_id"].as_str().unwrap();
+        assert_eq!(response["data"]["pubkey"], keystore.load(key_id).unwrap().pubkey().to_string());
+    }
+
+    #[tokio::test]
+    async fn mnemonic_generation_honours_body() {
+        let body = Bytes::from_static(br#"{"word_count": 24, "count": 2}"#);
+        let (status, response) = response_json(handle_mnemonic_generation(body).await).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response["data"]["mnemonic"].as_str().unwrap().split(' ').count(), 24);
+        assert_eq!(response["data"]["accounts"][1]["derivation_path"], "m/44'/501'/1'/0'");
+
+        let body = Bytes::from_static(br#"{"word_count": "24"}"#);
+        let (status, _) = response_json(handle_mnemonic_generation(body).await).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn expands_derivation_ranges() {
+        assert_eq!(expand_derivation_paths(None, None, None).unwrap(), [DEFAULT_DERIVATION_PATH]);
+        assert_eq!(
+            expand_derivation_paths(None, Some(3), Some(2)).unwrap(),
+            ["m/44'/501'/3'/0'", "m/44'/501'/4'/0'"]
+        );
+        assert!(expand_derivation_paths(Some("m/44'/501'/0'/0'"), None, Some(2)).is_err());
+        assert!(expand_derivation_paths(None, Some(u32::MAX), Some(2)).is_err());
+        assert!(expand_derivation_paths(None, None, Some(MAX_DERIVED_ACCOUNTS + 1)).is_err());
+    }
+}
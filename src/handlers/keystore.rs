@@ -0,0 +1,185 @@
+use axum::{extract::{Json, State}, response::IntoResponse};
+use solana_sdk::signature::Signer;
+use crate::models::request::{KeystoreDeleteInput, KeystoreExportInput};
+use crate::models::response::{KeystoreDeleteOutput, KeystoreExportOutput, KeystoreKeyOutput};
+use crate::services::keystore::run_blocking;
+use crate::state::AppState;
+use serde_json::json;
+
+pub async fn handle_keystore_list(State(state): State<AppState>) -> impl IntoResponse {
+    let keystore = match state.keystore {
+        Some(ref keystore) => keystore.clone(),
+        None => return build_error_response("Keystore is not configured"),
+    };
+    let stored_entries = match run_blocking(keystore, |keystore| keystore.list()).await {
+        Ok(entries) => entries,
+        Err(error) => return build_error_response(&error.to_string()),
+    };
+    let key_list: Vec<KeystoreKeyOutput> = stored_entries
+        .into_iter()
+        .map(|entry| KeystoreKeyOutput {
+            key_id: entry.key_id,
+            pubkey: entry.pubkey,
+            created_at: entry.created_at,
+        })
+        .collect();
+    build_success_response(key_list)
+}
+
+pub async fn handle_keystore_delete(
+    State(state): State<AppState>,
+    Json(input): Json<KeystoreDeleteInput>,
+) -> impl IntoResponse {
+    let keystore = match state.keystore {
+        Some(ref keystore) => keystore.clone(),
+        None => return build_error_response("Keystore is not configured"),
+    };
+    let key_id = match input.key_id {
+        Some(ref id) if !id.trim().is_empty() => id.trim(),
+        _ => return build_error_response("Missing required fields"),
+    };
+    let owned_key_id = key_id.to_string();
+    if let Err(error) = run_blocking(keystore, move |keystore| keystore.delete(&owned_key_id)).await {
+        return build_error_response(&error.to_string());
+    }
+    build_success_response(KeystoreDeleteOutput {
+        key_id: key_id.to_string(),
+        deleted: true,
+    })
+}
+
+pub async fn handle_keystore_export(
+    State(state): State<AppState>,
+    Json(input): Json<KeystoreExportInput>,
+) -> impl IntoResponse {
+    let keystore = match state.keystore {
+        Some(ref keystore) => keystore.clone(),
+        None => return build_error_response("Keystore is not configured"),
+    };
+    let key_id = match input.key_id {
+        Some(ref id) if !id.trim().is_empty() => id.trim(),
+        _ => return build_error_response("Missing required fields"),
+    };
+    let passphrase = match input.passphrase {
+        Some(ref phrase) if !phrase.is_empty() => phrase,
+        _ => return build_error_response("Missing required fields"),
+    };
+    let (owned_key_id, owned_passphrase) = (key_id.to_string(), passphrase.to_string());
+    let exported_keypair = match run_blocking(keystore, move |keystore| keystore.export(&owned_key_id, &owned_passphrase)).await {
+        Ok(keypair) => keypair,
+        Err(error) => return build_error_response(&error.to_string()),
+    };
+    build_success_response(KeystoreExportOutput {
+        key_id: key_id.to_string(),
+        pubkey: exported_keypair.pubkey().to_string(),
+        secret: bs58::encode(&exported_keypair.to_bytes()).into_string(),
+    })
+}
+
+fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use axum::http::StatusCode;
+    use solana_sdk::signature::Keypair;
+    use crate::services::keystore::Keystore;
+
+    const PASSPHRASE: &str = "correct horse battery staple";
+
+    async fn response_json(response: impl IntoResponse) -> (StatusCode, serde_json::Value) {
+        let response = response.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn keystore_state(directory: &tempfile::TempDir) -> (AppState, Arc<Keystore>) {
+        let keystore = Arc::new(Keystore::open(directory.path(), PASSPHRASE).unwrap());
+        let state = AppState {
+            keystore: Some(keystore.clone()),
+            ..AppState::default()
+        };
+        (state, keystore)
+    }
+
+    fn delete_input(key_id: &str) -> Json<KeystoreDeleteInput> {
+        Json(KeystoreDeleteInput { key_id: Some(key_id.to_string()) })
+    }
+
+    fn export_input(key_id: &str, passphrase: &str) -> Json<KeystoreExportInput> {
+        Json(KeystoreExportInput {
+            key_id: Some(key_id.to_string()),
+            passphrase: Some(passphrase.to_string()),
+        })
+    }
+
+    #[tokio::test]
+    async fn handlers_require_keystore() {
+        let (status, response) = response_json(handle_keystore_list(State(AppState::default())).await).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "Keystore is not configured");
+        let (status, _) = response_json(handle_keystore_delete(State(AppState::default()), delete_input("a")).await).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) =
+            response_json(handle_keystore_export(State(AppState::default()), export_input("a", PASSPHRASE)).await).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn list_returns_stored_keys() {
+        let directory = tempfile::tempdir().unwrap();
+        let (state, keystore) = keystore_state(&directory);
+        let entry = keystore.store(&Keypair::new()).unwrap();
+        let (status, response) = response_json(handle_keystore_list(State(state)).await).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response["data"].as_array().unwrap().len(), 1);
+        assert_eq!(response["data"][0]["key_id"], entry.key_id);
+        assert_eq!(response["data"][0]["pubkey"], entry.pubkey);
+        assert!(response["data"][0].get("secret").is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_removes_key() {
+        let directory = tempfile::tempdir().unwrap();
+        let (state, keystore) = keystore_state(&directory);
+        let entry = keystore.store(&Keypair::new()).unwrap();
+        let (status, response) =
+            response_json(handle_keystore_delete(State(state.clone()), delete_input(&entry.key_id)).await).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response["data"]["deleted"], true);
+        assert!(keystore.list().unwrap().is_empty());
+
+        let (status, response) = response_json(handle_keystore_delete(State(state.clone()), delete_input(&entry.key_id)).await).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "Key not found");
+        let (status, response) = response_json(handle_keystore_delete(State(state), delete_input("  ")).await).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "Missing required fields");
+    }
+
+    #[tokio::test]
+    async fn export_requires_matching_passphrase() {
+        let directory = tempfile::tempdir().unwrap();
+        let (state, keystore) = keystore_state(&directory);
+        let keypair = Keypair::new();
+        let entry = keystore.store(&keypair).unwrap();
+        let (status, response) =
+            response_json(handle_keystore_export(State(state.clone()), export_input(&entry.key_id, PASSPHRASE)).await).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response["data"]["pubkey"], keypair.pubkey().to_string());
+        assert_eq!(response["data"]["secret"], bs58::encode(&keypair.to_bytes()).into_string());
+
+        let (status, response) =
+            response_json(handle_keystore_export(State(state), export_input(&entry.key_id, "wrong")).await).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(response["error"], "Invalid keystore passphrase");
+    }
+}
@@ -0,0 +1,91 @@
+use serde_json::json;
+use axum::{extract::Json, response::IntoResponse};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use crate::handlers::compute_budget::build_budgeted_response;
+use crate::models::request::{ComputeBudgeted, MemoInput};
+use crate::models::response::InstructionOutput;
+use crate::utils::validation::*;
+
+pub fn build_memo_instruction(input: &MemoInput) -> Result<Instruction, String> {
+    let memo = match input.memo {
+        Some(ref text) if !text.is_empty() => text,
+        _ => return Err("Missing required fields".to_string()),
+    };
+    validate_memo(memo)?;
+    let signer_pubkeys = input.signers
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|signer| validate_pubkey_format(signer, "memo signer"))
+        .collect::<Result<Vec<Pubkey>, String>>()?;
+    let signer_refs: Vec<&Pubkey> = signer_pubkeys.iter().collect();
+    Ok(spl_memo::build_memo(memo.as_bytes(), &signer_refs))
+}
+
+/// Places the memo directly before the last (transfer) instruction, which is
+/// where Token-2022 accounts with required memos look for it.
+pub fn attach_memo(mut instructions: Vec<Instruction>, memo: Option<&str>) -> Result<Vec<Instruction>, String> {
+    let memo = match memo {
+        Some(text) if !text.is_empty() => text,
+        _ => return Ok(instructions),
+    };
+    validate_memo(memo)?;
+    let memo_index = instructions.len().saturating_sub(1);
+    instructions.insert(memo_index, spl_memo::build_memo(memo.as_bytes(), &[]));
+    Ok(instructions)
+}
+
+pub async fn handle_memo(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<MemoInput>>,
+) -> impl IntoResponse {
+    match build_memo_instruction(&input) {
+        Ok(memo_instruction) => build_budgeted_response(InstructionOutput::from(&memo_instruction), vec![memo_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::system_instruction;
+
+    fn memo_input(memo: &str, signers: Option<Vec<String>>) -> MemoInput {
+        MemoInput {
+            memo: Some(memo.to_string()),
+            signers,
+        }
+    }
+
+    #[test]
+    fn build_memo_instruction_marks_signers() {
+        let signer = Pubkey::new_unique();
+        let instruction = build_memo_instruction(&memo_input("hello", Some(vec![signer.to_string()]))).unwrap();
+        assert_eq!(instruction.program_id, spl_memo::ID);
+        assert_eq!(instruction.data, b"hello");
+        assert_eq!(instruction.accounts.len(), 1);
+        assert!(instruction.accounts[0].is_signer);
+        assert!(build_memo_instruction(&memo_input("hello", Some(vec!["bad".to_string()]))).is_err());
+        assert!(build_memo_instruction(&memo_input("", None)).is_err());
+    }
+
+    #[test]
+    fn attach_memo_inserts_before_last_instruction() {
+        let payer = Pubkey::new_unique();
+        let instructions = vec![
+            system_instruction::create_account(&payer, &Pubkey::new_unique(), 1, 0, &Pubkey::new_unique()),
+            system_instruction::transfer(&payer, &Pubkey::new_unique(), 1),
+        ];
+        let with_memo = attach_memo(instructions.clone(), Some("invoice 7")).unwrap();
+        assert_eq!(with_memo.len(), 3);
+        assert_eq!(with_memo[1].program_id, spl_memo::ID);
+        assert_eq!(with_memo[2], instructions[1]);
+        assert_eq!(attach_memo(instructions.clone(), None).unwrap(), instructions);
+        assert_eq!(attach_memo(instructions.clone(), Some("")).unwrap(), instructions);
+        assert!(attach_memo(instructions, Some(&"a".repeat(MAX_MEMO_BYTES + 1))).is_err());
+    }
+}
@@ -1,41 +1,70 @@
-use axum::{extract::Json, response::IntoResponse};
+use axum::{extract::{Json, State}, response::IntoResponse};
 use base64::{engine::general_purpose, Engine as _};
-use bs58;
-use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::offchain_message::{v0, MessageFormat, OffchainMessage};
+use solana_sdk::signature::{Signature, Signer};
 use crate::models::request::{MessageSigningInput, MessageVerificationInput};
 use crate::models::response::{SignatureOutput, VerificationOutput};
+use crate::services::keystore::resolve_keypair;
+use crate::state::AppState;
 use crate::utils::validation::*;
 use serde_json::json;
 
-pub async fn handle_message_signing(Json(input): Json<MessageSigningInput>) -> impl IntoResponse {
+const OFFCHAIN_MESSAGE_VERSION: u8 = 0;
+
+/// Wraps the message in the `\xffsolana offchain` envelope. The SDK picks the
+/// narrowest format the content fits (restricted ASCII, then limited UTF-8
+/// within the Ledger size, then extended UTF-8) and rejects anything longer.
+fn build_offchain_message(message_content: &str) -> Result<OffchainMessage, String> {
+    OffchainMessage::new(OFFCHAIN_MESSAGE_VERSION, message_content.as_bytes()).map_err(|_| {
+        format!("Message too long - maximum {} bytes for off-chain messages", v0::OffchainMessage::MAX_LEN)
+    })
+}
+
+fn offchain_format_name(message_format: MessageFormat) -> String {
+    match message_format {
+        MessageFormat::RestrictedAscii => "restricted_ascii",
+        MessageFormat::LimitedUtf8 => "limited_utf8",
+        MessageFormat::ExtendedUtf8 => "extended_utf8",
+    }
+    .to_string()
+}
+
+pub async fn handle_message_signing(
+    State(state): State<AppState>,
+    Json(input): Json<MessageSigningInput>,
+) -> impl IntoResponse {
     let message_content = match input.message {
         Some(ref msg) if !msg.trim().is_empty() => msg.trim(),
         _ => return build_error_response("Missing required fields"),
     };
-    let secret_key = match input.secret {
-        Some(ref key) if !key.trim().is_empty() => key.trim(),
-        _ => return build_error_response("Missing required fields"),
-    };
-    if let Err(error_msg) = validate_message_constraints(message_content) {
-        return build_error_response(&error_msg);
-    }
-    let secret_bytes = match bs58::decode(secret_key).into_vec() {
-        Ok(bytes) => bytes,
-        Err(_) => return build_error_response("Invalid secret key format"),
+    let offchain_message = if input.offchain.unwrap_or(false) {
+        match build_offchain_message(message_content) {
+            Ok(offchain_message) => Some(offchain_message),
+            Err(error_msg) => return build_error_response(&error_msg),
+        }
+    } else {
+        if let Err(error_msg) = validate_message_constraints(message_content) {
+            return build_error_response(&error_msg);
+        }
+        None
     };
-    if secret_bytes.len() != 64 {
-        return build_error_response("Invalid secret key length");
-    }
-    let signing_keypair = match Keypair::from_bytes(&secret_bytes) {
+    let signing_keypair = match resolve_keypair(state.keystore.as_ref(), input.secret.as_deref(), input.key_id.as_deref()).await {
         Ok(keypair) => keypair,
-        Err(_) => return build_error_response("Invalid secret key"),
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let message_signature = match offchain_message {
+        Some(ref offchain_message) => match offchain_message.sign(&signing_keypair) {
+            Ok(signature) => signature,
+            Err(_) => return build_error_response("Failed to serialize off-chain message"),
+        },
+        None => signing_keypair.sign_message(message_content.as_bytes()),
     };
-    let message_signature = signing_keypair.sign_message(message_content.as_bytes());
     let encoded_signature = general_purpose::STANDARD.encode(message_signature.as_ref());
     let signing_result = SignatureOutput {
         signature: encoded_signature,
         public_key: signing_keypair.pubkey().to_string(),
         message: message_content.to_string(),
+        message_format: offchain_message.map(|offchain_message| offchain_format_name(offchain_message.get_format())),
     };
     build_success_response(signing_result)
 }
@@ -53,9 +82,17 @@ pub async fn handle_message_verification(Json(input): Json<MessageVerificationIn
         Some(ref pk) if !pk.trim().is_empty() => pk.trim(),
         _ => return build_error_response("Missing required fields"),
     };
-    if let Err(error_msg) = validate_message_constraints(message_content) {
-        return build_error_response(&error_msg);
-    }
+    let offchain_message = if input.offchain.unwrap_or(false) {
+        match build_offchain_message(message_content) {
+            Ok(offchain_message) => Some(offchain_message),
+            Err(error_msg) => return build_error_response(&error_msg),
+        }
+    } else {
+        if let Err(error_msg) = validate_message_constraints(message_content) {
+            return build_error_response(&error_msg);
+        }
+        None
+    };
     let verification_pubkey = match crate::utils::validation::validate_pubkey_format(public_key_str, "public key") {
         Ok(pk) => pk,
         Err(error_msg) => return build_error_response(&error_msg),
@@ -71,11 +108,15 @@ pub async fn handle_message_verification(Json(input): Json<MessageVerificationIn
         Ok(sig) => sig,
         Err(_) => return build_error_response("Invalid signature"),
     };
-    let verification_result = decoded_signature.verify(&verification_pubkey.to_bytes(), message_content.as_bytes());
+    let verification_result = match offchain_message {
+        Some(ref offchain_message) => offchain_message.verify(&verification_pubkey, &decoded_signature).unwrap_or(false),
+        None => decoded_signature.verify(&verification_pubkey.to_bytes(), message_content.as_bytes()),
+    };
     let verification_output = VerificationOutput {
         valid: verification_result,
         message: message_content.to_string(),
         pubkey: public_key_str.to_string(),
+        message_format: offchain_message.map(|offchain_message| offchain_format_name(offchain_message.get_format())),
     };
     build_success_response(verification_output)
 }
@@ -86,4 +127,48 @@ fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCo
 
 fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
     (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Keypair;
+
+    fn format_of(message_content: &str) -> String {
+        offchain_format_name(build_offchain_message(message_content).unwrap().get_format())
+    }
+
+    #[test]
+    fn picks_narrowest_format_for_content() {
+        assert_eq!(format_of("Hello, world!"), "restricted_ascii");
+        assert_eq!(format_of("héllo"), "limited_utf8");
+        assert_eq!(format_of("line one\nline two"), "limited_utf8");
+        assert_eq!(format_of(&"a".repeat(v0::OffchainMessage::MAX_LEN_LEDGER)), "restricted_ascii");
+        assert_eq!(format_of(&"a".repeat(v0::OffchainMessage::MAX_LEN_LEDGER + 1)), "extended_utf8");
+        assert_eq!(format_of(&"a".repeat(v0::OffchainMessage::MAX_LEN)), "extended_utf8");
+    }
+
+    #[test]
+    fn rejects_messages_over_the_envelope_limit() {
+        let error_msg = build_offchain_message(&"a".repeat(v0::OffchainMessage::MAX_LEN + 1)).err().unwrap();
+        assert_eq!(error_msg, "Message too long - maximum 65515 bytes for off-chain messages");
+    }
+
+    #[test]
+    fn signs_the_serialized_envelope() {
+        let offchain_message = build_offchain_message("héllo").unwrap();
+        let envelope = offchain_message.serialize().unwrap();
+        let mut expected_envelope = b"\xffsolana offchain".to_vec();
+        expected_envelope.extend_from_slice(&[OFFCHAIN_MESSAGE_VERSION, 1]);
+        expected_envelope.extend_from_slice(&("héllo".len() as u16).to_le_bytes());
+        expected_envelope.extend_from_slice("héllo".as_bytes());
+        assert_eq!(envelope, expected_envelope);
+
+        let keypair = Keypair::new();
+        let envelope_signature = offchain_message.sign(&keypair).unwrap();
+        assert_eq!(envelope_signature, keypair.sign_message(&envelope));
+        assert!(offchain_message.verify(&keypair.pubkey(), &envelope_signature).unwrap());
+        let raw_signature = keypair.sign_message("héllo".as_bytes());
+        assert!(!offchain_message.verify(&keypair.pubkey(), &raw_signature).unwrap());
+    }
+}
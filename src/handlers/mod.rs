@@ -1,4 +1,14 @@
 pub mod keypair;
 pub mod token;
 pub mod message;
-pub mod sol;
\ No newline at end of file
+pub mod sol;
+pub mod transaction;
+pub mod keystore;
+pub mod rpc;
+pub mod compute_budget;
+pub mod stake;
+pub mod inspect;
+pub mod address;
+pub mod memo;
+pub mod solana_pay;
+pub mod siws;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use std::str::FromStr;
+use axum::{extract::{Json, State}, response::IntoResponse};
+use solana_sdk::signature::Signature;
+use crate::handlers::transaction::decode_transaction;
+use crate::models::request::{SignatureStatusInput, TransactionSubmissionInput};
+use crate::models::response::{BlockhashOutput, SignatureStatusOutput, TransactionSubmissionOutput};
+use crate::state::AppState;
+use serde_json::json;
+
+pub async fn handle_latest_blockhash(State(state): State<AppState>) -> impl IntoResponse {
+    let rpc_backend = match state.rpc {
+        Some(ref backend) => backend,
+        None => return build_error_response("RPC is not configured"),
+    };
+    match rpc_backend.get_latest_blockhash().await {
+        Ok(blockhash_info) => build_success_response(BlockhashOutput {
+            blockhash: blockhash_info.blockhash.to_string(),
+            last_valid_block_height: blockhash_info.last_valid_block_height,
+        }),
+        Err(error) => build_error_response(&error.to_string()),
+    }
+}
+
+pub async fn handle_transaction_submission(
+    State(state): State<AppState>,
+    Json(input): Json<TransactionSubmissionInput>,
+) -> impl IntoResponse {
+    let rpc_backend = match state.rpc {
+        Some(ref backend) => backend,
+        None => return build_error_response("RPC is not configured"),
+    };
+    let encoded_transaction = match input.transaction {
+        Some(ref tx) if !tx.trim().is_empty() => tx.trim(),
+        _ => return build_error_response("Missing required fields"),
+    };
+    let transaction = match decode_transaction(encoded_transaction) {
+        Ok(tx) => tx,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    if transaction.signatures.iter().any(|signature| *signature == Signature::default()) {
+        return build_error_response("Transaction is not fully signed");
+    }
+    match rpc_backend.send_transaction(&transaction).await {
+        Ok(signature) => build_success_response(TransactionSubmissionOutput {
+            signature: signature.to_string(),
+        }),
+        Err(error) => build_error_response(&error.to_string()),
+    }
+}
+
+pub async fn handle_signature_status(
+    State(state): State<AppState>,
+    Json(input): Json<SignatureStatusInput>,
+) -> impl IntoResponse {
+    let rpc_backend = match state.rpc {
+        Some(ref backend) => backend,
+        None => return build_error_response("RPC is not configured"),
+    };
+    let signature_str = match input.signature {
+        Some(ref sig) if !sig.trim().is_empty() => sig.trim(),
+        _ => return build_error_response("Missing required fields"),
+    };
+    let signature = match Signature::from_str(signature_str) {
+        Ok(sig) => sig,
+        Err(_) => return build_error_response("Invalid signature"),
+    };
+    let status = match rpc_backend.get_signature_status(&signature).await {
+        Ok(status) => status,
+        Err(error) => return build_error_response(&error.to_string()),
+    };
+    let status_output = match status {
+        Some(status) => SignatureStatusOutput {
+            signature: signature.to_string(),
+            found: true,
+            slot: Some(status.slot),
+            confirmations: status.confirmations,
+            confirmation_status: status.confirmation_status,
+            error: status.error,
+        },
+        None => SignatureStatusOutput {
+            signature: signature.to_string(),
+            found: false,
+            slot: None,
+            confirmations: None,
+            confirmation_status: None,
+            error: None,
+        },
+    };
+    build_success_response(status_output)
+}
+
+fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
@@ -0,0 +1,128 @@
+use axum::{extract::{Json, State}, response::IntoResponse};
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{Duration, Utc};
+use solana_sdk::signature::Signature;
+use crate::models::request::{SiwsChallengeInput, SiwsVerificationInput};
+use crate::models::response::{SiwsChallengeOutput, SiwsVerificationOutput};
+use crate::services::siws::{format_timestamp, SiwsMessage};
+use crate::state::AppState;
+use crate::utils::validation::*;
+use serde_json::json;
+
+const DEFAULT_CHALLENGE_TTL_SECONDS: u64 = 300;
+const MAX_CHALLENGE_TTL_SECONDS: u64 = 3600;
+const SESSION_TOKEN_TTL_SECONDS: i64 = 86_400;
+const SUPPORTED_CHAIN_IDS: [&str; 4] = ["mainnet", "devnet", "testnet", "localnet"];
+
+fn build_challenge(input: &SiwsChallengeInput) -> Result<SiwsMessage, String> {
+    let domain = match input.domain {
+        Some(ref domain) if !domain.trim().is_empty() => domain.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let address = match input.address {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    if domain.chars().any(char::is_whitespace) {
+        return Err("Invalid domain - must not contain whitespace".to_string());
+    }
+    let parsed_address = validate_pubkey_format(address, "address")?;
+    let statement = input.statement.as_deref().map(str::trim).filter(|statement| !statement.is_empty());
+    if statement.is_some_and(|statement| statement.contains('\n')) {
+        return Err("Invalid statement - must be a single line".to_string());
+    }
+    let uri = input.uri.as_deref().map(str::trim).filter(|uri| !uri.is_empty());
+    if uri.is_some_and(|uri| uri.chars().any(char::is_whitespace)) {
+        return Err("Invalid uri - must not contain whitespace".to_string());
+    }
+    let chain_id = input.chain_id.as_deref().map(str::trim).unwrap_or("mainnet");
+    if !SUPPORTED_CHAIN_IDS.contains(&chain_id.strip_prefix("solana:").unwrap_or(chain_id)) {
+        return Err("Invalid chain_id - expected mainnet, devnet, testnet or localnet".to_string());
+    }
+    let ttl_seconds = input.ttl_seconds.unwrap_or(DEFAULT_CHALLENGE_TTL_SECONDS);
+    if ttl_seconds == 0 || ttl_seconds > MAX_CHALLENGE_TTL_SECONDS {
+        return Err(format!("Invalid ttl_seconds - must be between 1 and {}", MAX_CHALLENGE_TTL_SECONDS));
+    }
+    let issued_at = Utc::now();
+    Ok(SiwsMessage::new(
+        domain,
+        &parsed_address.to_string(),
+        statement,
+        uri,
+        chain_id,
+        issued_at,
+        issued_at + Duration::seconds(ttl_seconds as i64),
+    ))
+}
+
+pub async fn handle_siws_challenge(
+    State(state): State<AppState>,
+    Json(input): Json<SiwsChallengeInput>,
+) -> impl IntoResponse {
+    let challenge = match build_challenge(&input) {
+        Ok(challenge) => challenge,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let challenge_output = SiwsChallengeOutput {
+        message: challenge.to_text(),
+        nonce: challenge.nonce.clone(),
+        issued_at: format_timestamp(&challenge.issued_at),
+        expiration_time: format_timestamp(&challenge.expiration_time),
+    };
+    if let Err(error_msg) = state.siws.insert(challenge) {
+        return build_error_response(&error_msg);
+    }
+    build_success_response(challenge_output)
+}
+
+pub async fn handle_siws_verification(
+    State(state): State<AppState>,
+    Json(input): Json<SiwsVerificationInput>,
+) -> impl IntoResponse {
+    let message_text = match input.message {
+        Some(ref msg) if !msg.is_empty() => msg,
+        _ => return build_error_response("Missing required fields"),
+    };
+    let signature_data = match input.signature {
+        Some(ref sig) if !sig.trim().is_empty() => sig.trim(),
+        _ => return build_error_response("Missing required fields"),
+    };
+    let signed_message = match SiwsMessage::parse(message_text) {
+        Ok(message) => message,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let signer_pubkey = match validate_pubkey_format(&signed_message.address, "address") {
+        Ok(pubkey) => pubkey,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let decoded_signature = match general_purpose::STANDARD.decode(signature_data) {
+        Ok(bytes) => match Signature::try_from(bytes.as_slice()) {
+            Ok(sig) => sig,
+            Err(_) => return build_error_response("Invalid signature length"),
+        },
+        Err(_) => return build_error_response("Invalid signature format"),
+    };
+    if !decoded_signature.verify(&signer_pubkey.to_bytes(), message_text.as_bytes()) {
+        return build_error_response("Invalid signature");
+    }
+    let now = Utc::now();
+    if let Err(error_msg) = state.siws.consume(&signed_message, now) {
+        return build_error_response(&error_msg);
+    }
+    let session_expires_at = now + Duration::seconds(SESSION_TOKEN_TTL_SECONDS);
+    build_success_response(SiwsVerificationOutput {
+        address: signed_message.address.clone(),
+        domain: signed_message.domain.clone(),
+        chain_id: signed_message.chain_id.clone(),
+        session_token: state.siws.issue_session_token(&signed_message, now, session_expires_at),
+        expires_at: format_timestamp(&session_expires_at),
+    })
+}
+
+fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
@@ -1,108 +1,343 @@
-use actix_web::{web, HttpResponse};
 use serde_json::json;
-use serde::Deserialize;
 use axum::{extract::Json, response::IntoResponse};
 use base64::{engine::general_purpose, Engine as _};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::nonce::State as NonceState;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
 use solana_sdk::system_instruction;
-use crate::models::request::SolTransferInput;
-use crate::models::response::SolTransferOutput;
+use crate::handlers::compute_budget::build_budgeted_response;
+use crate::handlers::memo::attach_memo;
+use crate::models::request::{
+    AllocateInput, AllocateWithSeedInput, AssignInput, ComputeBudgeted, CreateAccountInput, CreateAccountWithSeedInput,
+    NonceAdvanceInput, NonceAuthorizeInput, NonceCreationInput, NonceWithdrawInput, RentExemptionInput,
+    SolTransferInput, TransferWithSeedInput,
+};
+use crate::models::response::{InstructionOutput, RentExemptionOutput, SolTransferOutput};
 use crate::utils::validation::*;
 
-#[derive(Deserialize)]
-pub struct SendSolRequest {
-    pub from: String,
-    pub to: String,
-    pub lamports: u64,
-}
-
-#[derive(Deserialize)]
-pub struct SendTokenRequest {
-    pub destination: String,
-    pub mint: String,
-    pub owner: String,
-    pub amount: u64,
-}
-
-pub async fn send_sol(req: web::Json<SendSolRequest>) -> HttpResponse {
-    // Validate required fields
-    if req.from.is_empty() || req.to.is_empty() || req.lamports == 0 {
-        return HttpResponse::Ok().json(json!({
-            "success": false,
-            "error": "Missing or invalid required fields"
-        }));
-    }
-    // Implement SOL transfer logic here
-    HttpResponse::Ok().json(json!({
-        "success": true,
-        "data": {
-            "program_id": "11111111111111111111111111111111", // System Program ID
-            "accounts": [
-                req.from.clone(),
-                req.to.clone()
-            ],
-            "instruction_data": "instruction_data"
-        }
-    }))
-}
-
-pub async fn send_token(req: web::Json<SendTokenRequest>) -> HttpResponse {
-    // Validate required fields
-    if req.destination.is_empty() || req.mint.is_empty() || req.owner.is_empty() || req.amount == 0 {
-        return HttpResponse::Ok().json(json!({
-            "success": false,
-            "error": "Missing or invalid required fields"
-        }));
-    }
-    // Implement SPL token transfer logic here
-    HttpResponse::Ok().json(json!({
-        "success": true,
-        "data": {
-            "program_id": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA", // SPL Token Program ID
-            "accounts": [
-                {
-                    "pubkey": req.owner.clone(),
-                    "isSigner": true
-                },
-                {
-                    "pubkey": req.destination.clone(),
-                    "isSigner": false
-                }
-            ],
-            "instruction_data": "instruction_data"
-        }
-    }))
-}
-
-pub async fn handle_sol_transfer(Json(input): Json<SolTransferInput>) -> impl IntoResponse {
+pub fn build_sol_transfer_instruction(input: &SolTransferInput) -> Result<Instruction, String> {
     let source_address = match input.from {
         Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+        _ => return Err("Missing required fields".to_string()),
     };
     let destination_address = match input.to {
         Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+        _ => return Err("Missing required fields".to_string()),
     };
     let transfer_lamports = match input.lamports {
         Some(amount) => {
-            if let Err(error_msg) = validate_amount_bounds(amount, "lamports") {
-                return build_error_response(&error_msg);
-            }
+            validate_amount_bounds(amount, "lamports")?;
             amount
         },
-        None => return build_error_response("Missing required fields"),
+        None => return Err("Missing required fields".to_string()),
     };
-    let parsed_source = match validate_pubkey_format(source_address, "from") {
-        Ok(addr) => addr,
-        Err(error_msg) => return build_error_response(&error_msg),
+    let parsed_source = validate_pubkey_format(source_address, "from")?;
+    let parsed_destination = validate_pubkey_format(destination_address, "to")?;
+    if parsed_source == parsed_destination {
+        return Err("Cannot transfer to the same address".to_string());
+    }
+    Ok(system_instruction::transfer(&parsed_source, &parsed_destination, transfer_lamports))
+}
+
+pub fn rent_exempt_lamports(space: u64) -> u64 {
+    Rent::default().minimum_balance(space as usize)
+}
+
+pub fn build_sol_transfer_instructions(input: &SolTransferInput) -> Result<Vec<Instruction>, String> {
+    attach_memo(vec![build_sol_transfer_instruction(input)?], input.memo.as_deref())
+}
+
+pub fn build_create_account_instruction(input: &CreateAccountInput) -> Result<Instruction, String> {
+    let funding_address = match input.from {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let new_account_address = match input.new_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let account_space = validate_account_space(input.space.unwrap_or_default())?;
+    let account_lamports = resolve_account_lamports(input.lamports, account_space)?;
+    let parsed_funding = validate_pubkey_format(funding_address, "from")?;
+    let parsed_new_account = validate_pubkey_format(new_account_address, "new account")?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    if parsed_funding == parsed_new_account {
+        return Err("Funding account and new account cannot be the same".to_string());
+    }
+    Ok(system_instruction::create_account(
+        &parsed_funding,
+        &parsed_new_account,
+        account_lamports,
+        account_space,
+        &parsed_owner,
+    ))
+}
+
+pub fn build_allocate_instruction(input: &AllocateInput) -> Result<Instruction, String> {
+    let account_address = match input.account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let account_space = match input.space {
+        Some(space) => validate_account_space(space)?,
+        None => return Err("Missing required fields".to_string()),
+    };
+    let parsed_account = validate_pubkey_format(account_address, "account")?;
+    Ok(system_instruction::allocate(&parsed_account, account_space))
+}
+
+pub fn build_assign_instruction(input: &AssignInput) -> Result<Instruction, String> {
+    let account_address = match input.account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_account = validate_pubkey_format(account_address, "account")?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    Ok(system_instruction::assign(&parsed_account, &parsed_owner))
+}
+
+pub fn build_create_account_with_seed_instruction(input: &CreateAccountWithSeedInput) -> Result<Instruction, String> {
+    let funding_address = match input.from {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let base_address = match input.base {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let seed = input.seed.as_deref().ok_or_else(|| "Missing required fields".to_string())?;
+    let account_space = validate_account_space(input.space.unwrap_or_default())?;
+    let account_lamports = resolve_account_lamports(input.lamports, account_space)?;
+    let parsed_funding = validate_pubkey_format(funding_address, "from")?;
+    let parsed_base = validate_pubkey_format(base_address, "base")?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let derived_address = derive_seeded_address(&parsed_base, seed, &parsed_owner)?;
+    Ok(system_instruction::create_account_with_seed(
+        &parsed_funding,
+        &derived_address,
+        &parsed_base,
+        seed,
+        account_lamports,
+        account_space,
+        &parsed_owner,
+    ))
+}
+
+pub fn build_transfer_with_seed_instruction(input: &TransferWithSeedInput) -> Result<Instruction, String> {
+    let base_address = match input.base {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let from_owner_address = match input.from_owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let destination_address = match input.to {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let seed = input.seed.as_deref().ok_or_else(|| "Missing required fields".to_string())?;
+    let transfer_lamports = match input.lamports {
+        Some(amount) => {
+            validate_amount_bounds(amount, "lamports")?;
+            amount
+        },
+        None => return Err("Missing required fields".to_string()),
+    };
+    let parsed_base = validate_pubkey_format(base_address, "base")?;
+    let parsed_from_owner = validate_pubkey_format(from_owner_address, "from owner")?;
+    let parsed_destination = validate_pubkey_format(destination_address, "to")?;
+    let derived_source = derive_seeded_address(&parsed_base, seed, &parsed_from_owner)?;
+    if derived_source == parsed_destination {
+        return Err("Cannot transfer to the same address".to_string());
+    }
+    Ok(system_instruction::transfer_with_seed(
+        &derived_source,
+        &parsed_base,
+        seed.to_string(),
+        &parsed_from_owner,
+        &parsed_destination,
+        transfer_lamports,
+    ))
+}
+
+pub fn build_transfer_with_seed_instructions(input: &TransferWithSeedInput) -> Result<Vec<Instruction>, String> {
+    attach_memo(vec![build_transfer_with_seed_instruction(input)?], input.memo.as_deref())
+}
+
+pub fn build_allocate_with_seed_instruction(input: &AllocateWithSeedInput) -> Result<Instruction, String> {
+    let base_address = match input.base {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let seed = input.seed.as_deref().ok_or_else(|| "Missing required fields".to_string())?;
+    let account_space = match input.space {
+        Some(space) => validate_account_space(space)?,
+        None => return Err("Missing required fields".to_string()),
+    };
+    let parsed_base = validate_pubkey_format(base_address, "base")?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let derived_address = derive_seeded_address(&parsed_base, seed, &parsed_owner)?;
+    Ok(system_instruction::allocate_with_seed(
+        &derived_address,
+        &parsed_base,
+        seed,
+        account_space,
+        &parsed_owner,
+    ))
+}
+
+pub fn build_nonce_creation_instructions(input: &NonceCreationInput) -> Result<Vec<Instruction>, String> {
+    let funding_address = match input.from {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let nonce_address = match input.nonce_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_funding = validate_pubkey_format(funding_address, "from")?;
+    let parsed_nonce = validate_pubkey_format(nonce_address, "nonce account")?;
+    let parsed_authority = match input.authority {
+        Some(ref addr) if !addr.trim().is_empty() => validate_pubkey_format(addr, "nonce authority")?,
+        _ => parsed_funding,
+    };
+    if parsed_funding == parsed_nonce {
+        return Err("Funding account and nonce account cannot be the same".to_string());
+    }
+    let minimum_lamports = rent_exempt_lamports(NonceState::size() as u64);
+    let nonce_lamports = match input.lamports {
+        Some(amount) if amount < minimum_lamports => {
+            return Err(format!("Invalid lamports - nonce accounts need at least {} for rent exemption", minimum_lamports));
+        },
+        Some(amount) => {
+            validate_amount_bounds(amount, "lamports")?;
+            amount
+        },
+        None => minimum_lamports,
+    };
+    Ok(system_instruction::create_nonce_account(
+        &parsed_funding,
+        &parsed_nonce,
+        &parsed_authority,
+        nonce_lamports,
+    ))
+}
+
+pub fn build_nonce_advance_instruction(input: &NonceAdvanceInput) -> Result<Instruction, String> {
+    let nonce_address = match input.nonce_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let authority_address = match input.authority {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_nonce = validate_pubkey_format(nonce_address, "nonce account")?;
+    let parsed_authority = validate_pubkey_format(authority_address, "nonce authority")?;
+    Ok(system_instruction::advance_nonce_account(&parsed_nonce, &parsed_authority))
+}
+
+pub fn build_nonce_withdraw_instruction(input: &NonceWithdrawInput) -> Result<Instruction, String> {
+    let nonce_address = match input.nonce_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let authority_address = match input.authority {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let destination_address = match input.to {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let withdraw_lamports = match input.lamports {
+        Some(amount) => {
+            validate_amount_bounds(amount, "lamports")?;
+            amount
+        },
+        None => return Err("Missing required fields".to_string()),
     };
-    let parsed_destination = match validate_pubkey_format(destination_address, "to") {
-        Ok(addr) => addr,
+    let parsed_nonce = validate_pubkey_format(nonce_address, "nonce account")?;
+    let parsed_authority = validate_pubkey_format(authority_address, "nonce authority")?;
+    let parsed_destination = validate_pubkey_format(destination_address, "to")?;
+    if parsed_nonce == parsed_destination {
+        return Err("Cannot withdraw to the nonce account itself".to_string());
+    }
+    Ok(system_instruction::withdraw_nonce_account(
+        &parsed_nonce,
+        &parsed_authority,
+        &parsed_destination,
+        withdraw_lamports,
+    ))
+}
+
+pub fn build_nonce_authorize_instruction(input: &NonceAuthorizeInput) -> Result<Instruction, String> {
+    let nonce_address = match input.nonce_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let authority_address = match input.authority {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let new_authority_address = match input.new_authority {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_nonce = validate_pubkey_format(nonce_address, "nonce account")?;
+    let parsed_authority = validate_pubkey_format(authority_address, "nonce authority")?;
+    let parsed_new_authority = validate_pubkey_format(new_authority_address, "new authority")?;
+    Ok(system_instruction::authorize_nonce_account(
+        &parsed_nonce,
+        &parsed_authority,
+        &parsed_new_authority,
+    ))
+}
+
+fn resolve_account_lamports(lamports: Option<u64>, space: u64) -> Result<u64, String> {
+    match lamports {
+        Some(amount) => {
+            validate_amount_bounds(amount, "lamports")?;
+            Ok(amount)
+        },
+        None => Ok(rent_exempt_lamports(space)),
+    }
+}
+
+pub fn derive_seeded_address(base: &Pubkey, seed: &str, owner: &Pubkey) -> Result<Pubkey, String> {
+    Pubkey::create_with_seed(base, seed, owner)
+        .map_err(|error| format!("Invalid seed: {}", error))
+}
+
+pub async fn handle_sol_transfer(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<SolTransferInput>>,
+) -> impl IntoResponse {
+    let transfer_instructions = match build_sol_transfer_instructions(&input) {
+        Ok(instructions) => instructions,
         Err(error_msg) => return build_error_response(&error_msg),
     };
-    if parsed_source == parsed_destination {
-        return build_error_response("Cannot transfer to the same address");
+    if transfer_instructions.len() > 1 {
+        return build_budgeted_response((), transfer_instructions, &compute_budget);
     }
-    let transfer_instruction = system_instruction::transfer(&parsed_source, &parsed_destination, transfer_lamports);
+    let transfer_instruction = &transfer_instructions[0];
     let account_addresses: Vec<String> = transfer_instruction.accounts
         .iter()
         .map(|account| account.pubkey.to_string())
@@ -113,7 +348,115 @@ pub async fn handle_sol_transfer(Json(input): Json<SolTransferInput>) -> impl In
         accounts: account_addresses,
         instruction_data: encoded_instruction_data,
     };
-    build_success_response(transfer_result)
+    build_budgeted_response(transfer_result, transfer_instructions, &compute_budget)
+}
+
+pub async fn handle_create_account(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<CreateAccountInput>>,
+) -> impl IntoResponse {
+    match build_create_account_instruction(&input) {
+        Ok(create_instruction) => build_budgeted_response(InstructionOutput::from(&create_instruction), vec![create_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_allocate(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<AllocateInput>>,
+) -> impl IntoResponse {
+    match build_allocate_instruction(&input) {
+        Ok(allocate_instruction) => build_budgeted_response(InstructionOutput::from(&allocate_instruction), vec![allocate_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_assign(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<AssignInput>>,
+) -> impl IntoResponse {
+    match build_assign_instruction(&input) {
+        Ok(assign_instruction) => build_budgeted_response(InstructionOutput::from(&assign_instruction), vec![assign_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_create_account_with_seed(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<CreateAccountWithSeedInput>>,
+) -> impl IntoResponse {
+    match build_create_account_with_seed_instruction(&input) {
+        Ok(create_instruction) => build_budgeted_response(InstructionOutput::from(&create_instruction), vec![create_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_transfer_with_seed(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TransferWithSeedInput>>,
+) -> impl IntoResponse {
+    let transfer_instructions = match build_transfer_with_seed_instructions(&input) {
+        Ok(instructions) => instructions,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    if transfer_instructions.len() > 1 {
+        return build_budgeted_response((), transfer_instructions, &compute_budget);
+    }
+    build_budgeted_response(InstructionOutput::from(&transfer_instructions[0]), transfer_instructions, &compute_budget)
+}
+
+pub async fn handle_allocate_with_seed(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<AllocateWithSeedInput>>,
+) -> impl IntoResponse {
+    match build_allocate_with_seed_instruction(&input) {
+        Ok(allocate_instruction) => build_budgeted_response(InstructionOutput::from(&allocate_instruction), vec![allocate_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_rent_exemption(Json(input): Json<RentExemptionInput>) -> impl IntoResponse {
+    let account_space = match input.space {
+        Some(space) => match validate_account_space(space) {
+            Ok(space) => space,
+            Err(error_msg) => return build_error_response(&error_msg),
+        },
+        None => return build_error_response("Missing required fields"),
+    };
+    build_success_response(RentExemptionOutput {
+        space: account_space,
+        lamports: rent_exempt_lamports(account_space),
+    })
+}
+
+pub async fn handle_nonce_creation(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<NonceCreationInput>>,
+) -> impl IntoResponse {
+    match build_nonce_creation_instructions(&input) {
+        Ok(nonce_instructions) => build_budgeted_response((), nonce_instructions, &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_nonce_advance(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<NonceAdvanceInput>>,
+) -> impl IntoResponse {
+    match build_nonce_advance_instruction(&input) {
+        Ok(advance_instruction) => build_budgeted_response(InstructionOutput::from(&advance_instruction), vec![advance_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_nonce_withdraw(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<NonceWithdrawInput>>,
+) -> impl IntoResponse {
+    match build_nonce_withdraw_instruction(&input) {
+        Ok(withdraw_instruction) => build_budgeted_response(InstructionOutput::from(&withdraw_instruction), vec![withdraw_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_nonce_authorize(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<NonceAuthorizeInput>>,
+) -> impl IntoResponse {
+    match build_nonce_authorize_instruction(&input) {
+        Ok(authorize_instruction) => build_budgeted_response(InstructionOutput::from(&authorize_instruction), vec![authorize_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
 }
 
 fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
@@ -122,4 +465,282 @@ fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCo
 
 fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
     (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use solana_sdk::program_utils::limited_deserialize;
+    use solana_sdk::pubkey::MAX_SEED_LEN;
+    use solana_sdk::system_instruction::SystemInstruction;
+    use solana_sdk::system_program;
+    use solana_sdk::sysvar;
+
+    fn input<T: DeserializeOwned>(value: serde_json::Value) -> T {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn decode(instruction: &Instruction) -> SystemInstruction {
+        assert_eq!(instruction.program_id, system_program::ID);
+        limited_deserialize(&instruction.data).unwrap()
+    }
+
+    fn account_keys(instruction: &Instruction) -> Vec<Pubkey> {
+        instruction.accounts.iter().map(|account| account.pubkey).collect()
+    }
+
+    #[test]
+    fn transfer_attaches_memo_before_transfer() {
+        let (from, to) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let plain = build_sol_transfer_instructions(&input(json!({
+            "from": from.to_string(),
+            "to": to.to_string(),
+            "lamports": "5000",
+        })))
+        .unwrap();
+        assert_eq!(plain, [system_instruction::transfer(&from, &to, 5_000)]);
+
+        let with_memo = build_sol_transfer_instructions(&input(json!({
+            "from": from.to_string(),
+            "to": to.to_string(),
+            "lamports": 5_000,
+            "memo": "rent for march",
+        })))
+        .unwrap();
+        assert_eq!(with_memo.len(), 2);
+        assert_eq!(with_memo[0].program_id, spl_memo::ID);
+        assert_eq!(with_memo[0].data, b"rent for march");
+        assert_eq!(decode(&with_memo[1]), SystemInstruction::Transfer { lamports: 5_000 });
+
+        let oversized_memo = build_sol_transfer_instructions(&input(json!({
+            "from": from.to_string(),
+            "to": to.to_string(),
+            "lamports": 5_000,
+            "memo": "a".repeat(MAX_MEMO_BYTES + 1),
+        })));
+        assert!(oversized_memo.is_err());
+        let to_self = build_sol_transfer_instructions(&input(json!({
+            "from": from.to_string(),
+            "to": from.to_string(),
+            "lamports": 5_000,
+        })));
+        assert_eq!(to_self.unwrap_err(), "Cannot transfer to the same address");
+    }
+
+    #[test]
+    fn create_account_passes_lamports_and_space() {
+        let (from, new_account, owner) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let funded = build_create_account_instruction(&input(json!({
+            "from": from.to_string(),
+            "new_account": new_account.to_string(),
+            "owner": owner.to_string(),
+            "lamports": 2_000_000,
+            "space": 165,
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&funded), [from, new_account]);
+        assert_eq!(decode(&funded), SystemInstruction::CreateAccount { lamports: 2_000_000, space: 165, owner });
+
+        let rent_exempt = build_create_account_instruction(&input(json!({
+            "from": from.to_string(),
+            "new_account": new_account.to_string(),
+            "owner": owner.to_string(),
+            "space": 165,
+        })))
+        .unwrap();
+        assert_eq!(
+            decode(&rent_exempt),
+            SystemInstruction::CreateAccount { lamports: rent_exempt_lamports(165), space: 165, owner }
+        );
+
+        let same_account = build_create_account_instruction(&input(json!({
+            "from": from.to_string(),
+            "new_account": from.to_string(),
+            "owner": owner.to_string(),
+        })));
+        assert_eq!(same_account.unwrap_err(), "Funding account and new account cannot be the same");
+    }
+
+    #[test]
+    fn allocate_and_assign() {
+        let (account, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let allocate = build_allocate_instruction(&input(json!({ "account": account.to_string(), "space": 512 }))).unwrap();
+        assert_eq!(account_keys(&allocate), [account]);
+        assert_eq!(decode(&allocate), SystemInstruction::Allocate { space: 512 });
+        let missing_space = build_allocate_instruction(&input(json!({ "account": account.to_string() })));
+        assert_eq!(missing_space.unwrap_err(), "Missing required fields");
+
+        let assign = build_assign_instruction(&input(json!({
+            "account": account.to_string(),
+            "owner": owner.to_string(),
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&assign), [account]);
+        assert_eq!(decode(&assign), SystemInstruction::Assign { owner });
+    }
+
+    #[test]
+    fn seeded_builders_use_create_with_seed_address() {
+        let (from, base, owner, to) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let seed = "vault-1";
+        let derived = Pubkey::create_with_seed(&base, seed, &owner).unwrap();
+        assert_eq!(derive_seeded_address(&base, seed, &owner), Ok(derived));
+
+        let create = build_create_account_with_seed_instruction(&input(json!({
+            "from": from.to_string(),
+            "base": base.to_string(),
+            "seed": seed,
+            "owner": owner.to_string(),
+            "lamports": 1_000_000,
+            "space": 64,
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&create), [from, derived, base]);
+        assert_eq!(decode(&create), SystemInstruction::CreateAccountWithSeed {
+            base,
+            seed: seed.to_string(),
+            lamports: 1_000_000,
+            space: 64,
+            owner,
+        });
+
+        let transfer = build_transfer_with_seed_instructions(&input(json!({
+            "base": base.to_string(),
+            "seed": seed,
+            "from_owner": owner.to_string(),
+            "to": to.to_string(),
+            "lamports": 750,
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&transfer[0]), [derived, base, to]);
+        assert_eq!(decode(&transfer[0]), SystemInstruction::TransferWithSeed {
+            lamports: 750,
+            from_seed: seed.to_string(),
+            from_owner: owner,
+        });
+
+        let allocate = build_allocate_with_seed_instruction(&input(json!({
+            "base": base.to_string(),
+            "seed": seed,
+            "owner": owner.to_string(),
+            "space": 128,
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&allocate), [derived, base]);
+        assert_eq!(decode(&allocate), SystemInstruction::AllocateWithSeed {
+            base,
+            seed: seed.to_string(),
+            space: 128,
+            owner,
+        });
+    }
+
+    #[test]
+    fn seeded_builders_reject_invalid_seeds() {
+        let (base, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let long_seed = "s".repeat(MAX_SEED_LEN + 1);
+        let create = build_create_account_with_seed_instruction(&input(json!({
+            "from": Pubkey::new_unique().to_string(),
+            "base": base.to_string(),
+            "seed": long_seed,
+            "owner": owner.to_string(),
+        })));
+        assert!(create.unwrap_err().starts_with("Invalid seed"));
+        let transfer = build_transfer_with_seed_instruction(&input(json!({
+            "base": base.to_string(),
+            "seed": long_seed,
+            "from_owner": owner.to_string(),
+            "to": Pubkey::new_unique().to_string(),
+            "lamports": 1,
+        })));
+        assert!(transfer.unwrap_err().starts_with("Invalid seed"));
+        let allocate = build_allocate_with_seed_instruction(&input(json!({
+            "base": base.to_string(),
+            "seed": long_seed,
+            "owner": owner.to_string(),
+            "space": 1,
+        })));
+        assert!(allocate.unwrap_err().starts_with("Invalid seed"));
+        let missing_seed = build_allocate_with_seed_instruction(&input(json!({
+            "base": base.to_string(),
+            "owner": owner.to_string(),
+            "space": 1,
+        })));
+        assert_eq!(missing_seed.unwrap_err(), "Missing required fields");
+    }
+
+    #[test]
+    fn nonce_creation_funds_and_initializes() {
+        let (from, nonce_account, authority) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let minimum_lamports = rent_exempt_lamports(NonceState::size() as u64);
+        let nonce_instructions = build_nonce_creation_instructions(&input(json!({
+            "from": from.to_string(),
+            "nonce_account": nonce_account.to_string(),
+            "authority": authority.to_string(),
+        })))
+        .unwrap();
+        assert_eq!(nonce_instructions.len(), 2);
+        assert_eq!(decode(&nonce_instructions[0]), SystemInstruction::CreateAccount {
+            lamports: minimum_lamports,
+            space: NonceState::size() as u64,
+            owner: system_program::ID,
+        });
+        assert_eq!(account_keys(&nonce_instructions[1]), [nonce_account, sysvar::recent_blockhashes::ID, sysvar::rent::ID]);
+        assert_eq!(decode(&nonce_instructions[1]), SystemInstruction::InitializeNonceAccount(authority));
+
+        let default_authority = build_nonce_creation_instructions(&input(json!({
+            "from": from.to_string(),
+            "nonce_account": nonce_account.to_string(),
+        })))
+        .unwrap();
+        assert_eq!(decode(&default_authority[1]), SystemInstruction::InitializeNonceAccount(from));
+
+        let underfunded = build_nonce_creation_instructions(&input(json!({
+            "from": from.to_string(),
+            "nonce_account": nonce_account.to_string(),
+            "lamports": minimum_lamports - 1,
+        })));
+        assert!(underfunded.unwrap_err().contains("rent exemption"));
+    }
+
+    #[test]
+    fn nonce_advance_withdraw_and_authorize() {
+        let (nonce_account, authority, to) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let advance = build_nonce_advance_instruction(&input(json!({
+            "nonce_account": nonce_account.to_string(),
+            "authority": authority.to_string(),
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&advance), [nonce_account, sysvar::recent_blockhashes::ID, authority]);
+        assert_eq!(decode(&advance), SystemInstruction::AdvanceNonceAccount);
+
+        let withdraw = build_nonce_withdraw_instruction(&input(json!({
+            "nonce_account": nonce_account.to_string(),
+            "authority": authority.to_string(),
+            "to": to.to_string(),
+            "lamports": 42,
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&withdraw)[..2], [nonce_account, to]);
+        assert_eq!(decode(&withdraw), SystemInstruction::WithdrawNonceAccount(42));
+        let withdraw_to_self = build_nonce_withdraw_instruction(&input(json!({
+            "nonce_account": nonce_account.to_string(),
+            "authority": authority.to_string(),
+            "to": nonce_account.to_string(),
+            "lamports": 42,
+        })));
+        assert_eq!(withdraw_to_self.unwrap_err(), "Cannot withdraw to the nonce account itself");
+
+        let new_authority = Pubkey::new_unique();
+        let authorize = build_nonce_authorize_instruction(&input(json!({
+            "nonce_account": nonce_account.to_string(),
+            "authority": authority.to_string(),
+            "new_authority": new_authority.to_string(),
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&authorize), [nonce_account, authority]);
+        assert_eq!(decode(&authorize), SystemInstruction::AuthorizeNonceAccount(new_authority));
+    }
+}
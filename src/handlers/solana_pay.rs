@@ -0,0 +1,519 @@
+use serde_json::json;
+use axum::{extract::{Json, Path, RawQuery, State}, response::IntoResponse};
+use base64::{engine::general_purpose, Engine as _};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use qrcode::{Color, QrCode};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use crate::handlers::transaction::{compile_transaction, TransactionVersion};
+use crate::handlers::{sol, token};
+use crate::models::request::{
+    SolTransferInput, SolanaPayAccountInput, SolanaPayParseInput, SolanaPayUrlInput, TokenTransferInput,
+};
+use crate::models::response::{
+    QrCodeOutput, SolanaPayMetadataOutput, SolanaPayTransactionOutput, SolanaPayTransferRequestOutput,
+    SolanaPayUrlOutput,
+};
+use crate::services::pay_templates::PayTemplate;
+use crate::state::AppState;
+use crate::utils::validation::*;
+
+const SOLANA_PAY_SCHEME: &str = "solana:";
+const QR_MODULE_PIXELS: usize = 8;
+const QR_QUIET_ZONE_MODULES: usize = 4;
+const QR_SVG_MIN_DIMENSION: u32 = 256;
+
+/// Characters left unescaped by JavaScript's `encodeURIComponent`, which is
+/// what the Solana Pay spec uses for query values.
+const URI_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')');
+
+fn parse_pay_amount(amount: &str, decimals: u8) -> Result<u64, String> {
+    if amount.starts_with('.') || amount.ends_with('.') {
+        return Err("Invalid amount format".to_string());
+    }
+    parse_ui_amount(amount, decimals).map_err(|error_msg| error_msg.replacen("ui_amount", "amount", 1))
+}
+
+/// SOL amounts always use lamport precision; token amounts are checked
+/// against the mint decimals when given and the 9-decimal maximum otherwise.
+fn amount_decimals(spl_token: Option<&Pubkey>, decimals: Option<u8>) -> Result<u8, String> {
+    match decimals {
+        Some(decimals) if spl_token.is_some() => {
+            validate_token_decimals(decimals)?;
+            Ok(decimals)
+        },
+        _ => Ok(SOL_DECIMALS),
+    }
+}
+
+pub fn build_transfer_request_url(input: &SolanaPayUrlInput) -> Result<String, String> {
+    let recipient_address = match input.recipient {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_recipient = validate_pubkey_format(recipient_address, "recipient")?;
+    let parsed_token = match input.spl_token {
+        Some(ref addr) if !addr.trim().is_empty() => Some(validate_pubkey_format(addr, "spl-token")?),
+        _ => None,
+    };
+    if let Some(memo) = input.memo.as_deref().filter(|memo| !memo.is_empty()) {
+        validate_memo(memo)?;
+    }
+    let mut query_params: Vec<(&str, String)> = Vec::new();
+    if let Some(ref amount) = input.amount {
+        let amount = amount.trim();
+        parse_pay_amount(amount, amount_decimals(parsed_token.as_ref(), input.decimals)?)?;
+        query_params.push(("amount", amount.to_string()));
+    }
+    if let Some(token) = parsed_token {
+        query_params.push(("spl-token", token.to_string()));
+    }
+    for reference in input.references.as_deref().unwrap_or_default() {
+        query_params.push(("reference", validate_pubkey_format(reference, "reference")?.to_string()));
+    }
+    for (param_name, value) in [("label", &input.label), ("message", &input.message), ("memo", &input.memo)] {
+        if let Some(text) = value.as_deref().filter(|text| !text.is_empty()) {
+            query_params.push((param_name, utf8_percent_encode(text, URI_COMPONENT).to_string()));
+        }
+    }
+    let query_string = query_params
+        .iter()
+        .map(|(param_name, value)| format!("{}={}", param_name, value))
+        .collect::<Vec<String>>()
+        .join("&");
+    let mut transfer_url = format!("{}{}", SOLANA_PAY_SCHEME, parsed_recipient);
+    if !query_string.is_empty() {
+        transfer_url.push('?');
+        transfer_url.push_str(&query_string);
+    }
+    Ok(transfer_url)
+}
+
+pub fn parse_transfer_request_url(input: &SolanaPayParseInput) -> Result<SolanaPayTransferRequestOutput, String> {
+    let transfer_url = match input.url {
+        Some(ref url) if !url.trim().is_empty() => url.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let url_body = transfer_url
+        .strip_prefix(SOLANA_PAY_SCHEME)
+        .ok_or_else(|| "Invalid Solana Pay URL - expected the solana: scheme".to_string())?;
+    let (recipient_part, query_string) = url_body.split_once('?').unwrap_or((url_body, ""));
+    let decoded_recipient = percent_decode_str(recipient_part).decode_utf8_lossy();
+    if decoded_recipient.starts_with("https://") || decoded_recipient.starts_with("http://") {
+        return Err("Transaction request URLs are not transfer requests".to_string());
+    }
+    let parsed_recipient = validate_pubkey_format(recipient_part, "recipient")?;
+    let mut transfer_request = SolanaPayTransferRequestOutput {
+        recipient: parsed_recipient.to_string(),
+        amount: None,
+        base_amount: None,
+        spl_token: None,
+        references: Vec::new(),
+        label: None,
+        message: None,
+        memo: None,
+    };
+    for (param_name, value) in form_urlencoded::parse(query_string.as_bytes()) {
+        let value = value.into_owned();
+        let field = match param_name.as_ref() {
+            "reference" => {
+                transfer_request.references.push(validate_pubkey_format(&value, "reference")?.to_string());
+                continue;
+            },
+            "amount" => &mut transfer_request.amount,
+            "spl-token" => &mut transfer_request.spl_token,
+            "label" => &mut transfer_request.label,
+            "message" => &mut transfer_request.message,
+            "memo" => &mut transfer_request.memo,
+            _ => continue,
+        };
+        if field.is_some() {
+            return Err(format!("Invalid Solana Pay URL - duplicate {} parameter", param_name));
+        }
+        *field = Some(value);
+    }
+    let parsed_token = match transfer_request.spl_token {
+        Some(ref token) => Some(validate_pubkey_format(token, "spl-token")?),
+        None => None,
+    };
+    if let Some(ref amount) = transfer_request.amount {
+        let decimals = amount_decimals(parsed_token.as_ref(), input.decimals)?;
+        let base_amount = parse_pay_amount(amount, decimals)?;
+        if parsed_token.is_none() || input.decimals.is_some() {
+            transfer_request.base_amount = Some(base_amount);
+        }
+    }
+    Ok(transfer_request)
+}
+
+pub fn render_qr_code(content: &str, format: &str) -> Result<QrCodeOutput, String> {
+    let qr_code = QrCode::new(content.as_bytes()).map_err(|error| format!("Unable to encode QR code: {}", error))?;
+    match format.trim() {
+        "svg" => {
+            let svg_image = qr_code
+                .render::<qrcode::render::svg::Color>()
+                .min_dimensions(QR_SVG_MIN_DIMENSION, QR_SVG_MIN_DIMENSION)
+                .build();
+            Ok(QrCodeOutput {
+                format: "svg".to_string(),
+                content_type: "image/svg+xml".to_string(),
+                data: svg_image,
+            })
+        },
+        "png" => Ok(QrCodeOutput {
+            format: "png".to_string(),
+            content_type: "image/png".to_string(),
+            data: general_purpose::STANDARD.encode(encode_qr_png(&qr_code)?),
+        }),
+        _ => Err("Invalid qr_format - expected \"svg\" or \"png\"".to_string()),
+    }
+}
+
+fn encode_qr_png(qr_code: &QrCode) -> Result<Vec<u8>, String> {
+    let module_count = qr_code.width();
+    let modules = qr_code.to_colors();
+    let image_side = (module_count + 2 * QR_QUIET_ZONE_MODULES) * QR_MODULE_PIXELS;
+    let mut pixels = vec![u8::MAX; image_side * image_side];
+    for (index, module) in modules.iter().enumerate() {
+        if *module != Color::Dark {
+            continue;
+        }
+        let module_x = (index % module_count + QR_QUIET_ZONE_MODULES) * QR_MODULE_PIXELS;
+        let module_y = (index / module_count + QR_QUIET_ZONE_MODULES) * QR_MODULE_PIXELS;
+        for row in module_y..module_y + QR_MODULE_PIXELS {
+            pixels[row * image_side + module_x..row * image_side + module_x + QR_MODULE_PIXELS].fill(0);
+        }
+    }
+    let mut png_bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_bytes, image_side as u32, image_side as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|error| format!("Unable to encode PNG: {}", error))?;
+    writer.write_image_data(&pixels).map_err(|error| format!("Unable to encode PNG: {}", error))?;
+    writer.finish().map_err(|error| format!("Unable to encode PNG: {}", error))?;
+    Ok(png_bytes)
+}
+
+/// Builds the payment for a transaction request. Reference keys are appended
+/// to the transfer instruction as read-only accounts so the payment can be
+/// found later with `getSignaturesForAddress`.
+pub fn build_template_instructions(
+    template: &PayTemplate,
+    account: &Pubkey,
+    amount: &str,
+    references: &[Pubkey],
+) -> Result<Vec<Instruction>, String> {
+    let mut instructions = match template.spl_token {
+        None => sol::build_sol_transfer_instructions(&SolTransferInput {
+            from: Some(account.to_string()),
+            to: Some(template.recipient.clone()),
+            lamports: Some(parse_pay_amount(amount, SOL_DECIMALS)?),
+            memo: template.memo.clone(),
+        })?,
+        Some(ref mint) => token::build_token_transfer_instructions(&TokenTransferInput {
+            destination: None,
+            destination_owner: Some(template.recipient.clone()),
+            payer: Some(account.to_string()),
+            mint: Some(mint.clone()),
+            owner: Some(account.to_string()),
+            multisig_signers: None,
+            amount: None,
+            ui_amount: Some(amount.to_string()),
+            decimals: template.decimals,
+            mint_account_data: None,
+            checked: Some(true),
+            token_program: template.token_program.clone(),
+            memo: template.memo.clone(),
+        })?,
+    };
+    if let Some(transfer_instruction) = instructions.last_mut() {
+        transfer_instruction.accounts.extend(
+            references.iter().map(|reference| AccountMeta::new_readonly(*reference, false)),
+        );
+    }
+    Ok(instructions)
+}
+
+fn parse_request_query(query: Option<&str>) -> Result<(Option<String>, Vec<Pubkey>), String> {
+    let mut amount = None;
+    let mut references = Vec::new();
+    for (param_name, value) in form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+        match param_name.as_ref() {
+            "amount" => amount = Some(value.into_owned()),
+            "reference" => references.push(validate_pubkey_format(&value, "reference")?),
+            _ => {},
+        }
+    }
+    Ok((amount, references))
+}
+
+pub async fn handle_transaction_request_metadata(
+    State(state): State<AppState>,
+    Path(template_name): Path<String>,
+) -> impl IntoResponse {
+    let pay_templates = match state.pay_templates {
+        Some(ref templates) => templates,
+        None => return build_error_response("Solana Pay templates are not configured"),
+    };
+    match pay_templates.get(&template_name) {
+        Some(template) => build_protocol_response(SolanaPayMetadataOutput {
+            label: template.label.clone(),
+            icon: template.icon.clone(),
+        }),
+        None => build_error_response("Unknown payment template"),
+    }
+}
+
+pub async fn handle_transaction_request(
+    State(state): State<AppState>,
+    Path(template_name): Path<String>,
+    RawQuery(query): RawQuery,
+    Json(input): Json<SolanaPayAccountInput>,
+) -> impl IntoResponse {
+    let pay_templates = match state.pay_templates {
+        Some(ref templates) => templates,
+        None => return build_error_response("Solana Pay templates are not configured"),
+    };
+    let rpc_backend = match state.rpc {
+        Some(ref backend) => backend,
+        None => return build_error_response("RPC is not configured"),
+    };
+    let template = match pay_templates.get(&template_name) {
+        Some(template) => template,
+        None => return build_error_response("Unknown payment template"),
+    };
+    let parsed_account = match input.account {
+        Some(ref addr) if !addr.trim().is_empty() => match validate_pubkey_format(addr, "account") {
+            Ok(pubkey) => pubkey,
+            Err(error_msg) => return build_error_response(&error_msg),
+        },
+        _ => return build_error_response("Missing required fields"),
+    };
+    let (query_amount, mut references) = match parse_request_query(query.as_deref()) {
+        Ok(parsed) => parsed,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let amount = match template.amount.as_deref().or(query_amount.as_deref()) {
+        Some(amount) => amount.trim().to_string(),
+        None => return build_error_response("Missing amount - set it on the template or the request URL"),
+    };
+    references.extend(template.references.iter().filter_map(|reference| reference.parse::<Pubkey>().ok()));
+    if references.is_empty() {
+        references.push(Keypair::new().pubkey());
+    }
+    let instructions = match build_template_instructions(template, &parsed_account, &amount, &references) {
+        Ok(instructions) => instructions,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let blockhash_info = match rpc_backend.get_latest_blockhash().await {
+        Ok(info) => info,
+        Err(error) => return build_error_response(&error.to_string()),
+    };
+    match compile_transaction(&instructions, &parsed_account, &blockhash_info.blockhash, TransactionVersion::Legacy, &[]) {
+        Ok(compiled) => build_protocol_response(SolanaPayTransactionOutput {
+            transaction: compiled.transaction,
+            message: template.message.clone(),
+            references: references.iter().map(Pubkey::to_string).collect(),
+        }),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_transfer_request_url(Json(input): Json<SolanaPayUrlInput>) -> impl IntoResponse {
+    let transfer_url = match build_transfer_request_url(&input) {
+        Ok(url) => url,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let qr_code = match input.qr_format {
+        Some(ref format) if !format.trim().is_empty() => match render_qr_code(&transfer_url, format) {
+            Ok(rendered) => Some(rendered),
+            Err(error_msg) => return build_error_response(&error_msg),
+        },
+        _ => None,
+    };
+    build_success_response(SolanaPayUrlOutput { url: transfer_url, qr_code })
+}
+
+pub async fn handle_transfer_request_parsing(Json(input): Json<SolanaPayParseInput>) -> impl IntoResponse {
+    match parse_transfer_request_url(&input) {
+        Ok(transfer_request) => build_success_response(transfer_request),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+/// Wallets read transaction-request fields at the top level, so these
+/// responses skip the usual success envelope.
+fn build_protocol_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!(data)))
+}
+
+fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC_RECIPIENT: &str = "mvines9iiHiAbtM2emGd7N7NpPNCcKqtK2bmqwvdJwJ";
+
+    fn url_input(recipient: &str) -> SolanaPayUrlInput {
+        SolanaPayUrlInput {
+            recipient: Some(recipient.to_string()),
+            amount: None,
+            decimals: None,
+            spl_token: None,
+            references: None,
+            label: None,
+            message: None,
+            memo: None,
+            qr_format: None,
+        }
+    }
+
+    fn parse(url: &str, decimals: Option<u8>) -> Result<SolanaPayTransferRequestOutput, String> {
+        parse_transfer_request_url(&SolanaPayParseInput {
+            url: Some(url.to_string()),
+            decimals,
+        })
+    }
+
+    #[test]
+    fn build_matches_spec_example() {
+        let input = SolanaPayUrlInput {
+            amount: Some("1".to_string()),
+            label: Some("Michael".to_string()),
+            message: Some("Thanks for all the fish".to_string()),
+            memo: Some("OrderId12345".to_string()),
+            ..url_input(SPEC_RECIPIENT)
+        };
+        assert_eq!(
+            build_transfer_request_url(&input).unwrap(),
+            format!("solana:{}?amount=1&label=Michael&message=Thanks%20for%20all%20the%20fish&memo=OrderId12345", SPEC_RECIPIENT),
+        );
+    }
+
+    #[test]
+    fn build_and_parse_round_trip() {
+        let mint = Pubkey::new_unique();
+        let references = vec![Pubkey::new_unique().to_string(), Pubkey::new_unique().to_string()];
+        let input = SolanaPayUrlInput {
+            amount: Some("0.01".to_string()),
+            decimals: Some(6),
+            spl_token: Some(mint.to_string()),
+            references: Some(references.clone()),
+            label: Some("Café & Co".to_string()),
+            message: Some("50% off?".to_string()),
+            memo: Some("#4 = ok".to_string()),
+            ..url_input(SPEC_RECIPIENT)
+        };
+        let transfer_url = build_transfer_request_url(&input).unwrap();
+        let transfer_request = parse(&transfer_url, Some(6)).unwrap();
+        assert_eq!(transfer_request.recipient, SPEC_RECIPIENT);
+        assert_eq!(transfer_request.amount.as_deref(), Some("0.01"));
+        assert_eq!(transfer_request.base_amount, Some(10_000));
+        assert_eq!(transfer_request.spl_token, Some(mint.to_string()));
+        assert_eq!(transfer_request.references, references);
+        assert_eq!(transfer_request.label.as_deref(), Some("Café & Co"));
+        assert_eq!(transfer_request.message.as_deref(), Some("50% off?"));
+        assert_eq!(transfer_request.memo.as_deref(), Some("#4 = ok"));
+    }
+
+    #[test]
+    fn token_base_amount_requires_known_decimals() {
+        let transfer_url = format!("solana:{}?amount=1.5&spl-token={}", SPEC_RECIPIENT, Pubkey::new_unique());
+        assert_eq!(parse(&transfer_url, None).unwrap().base_amount, None);
+        assert_eq!(parse(&transfer_url, Some(2)).unwrap().base_amount, Some(150));
+        let sol_url = format!("solana:{}?amount=1.5", SPEC_RECIPIENT);
+        assert_eq!(parse(&sol_url, None).unwrap().base_amount, Some(1_500_000_000));
+    }
+
+    #[test]
+    fn rejects_malformed_urls() {
+        assert!(parse("https://example.com", None).is_err());
+        assert!(parse("solana:https%3A%2F%2Fexample.com%2Fpay", None).is_err());
+        assert!(parse(&format!("solana:{}?amount=.5", SPEC_RECIPIENT), None).is_err());
+        assert!(parse(&format!("solana:{}?amount=1&amount=2", SPEC_RECIPIENT), None).is_err());
+        assert!(parse(&format!("solana:{}?amount=0.0000000001", SPEC_RECIPIENT), None).is_err());
+        assert!(parse(&format!("solana:{}?reference=bad", SPEC_RECIPIENT), None).is_err());
+        let bad_amount = SolanaPayUrlInput {
+            amount: Some("1.".to_string()),
+            ..url_input(SPEC_RECIPIENT)
+        };
+        assert!(build_transfer_request_url(&bad_amount).is_err());
+    }
+
+    fn pay_template(spl_token: Option<Pubkey>, decimals: Option<u8>) -> PayTemplate {
+        PayTemplate {
+            label: "Coffee".to_string(),
+            icon: "https://example.com/icon.svg".to_string(),
+            recipient: SPEC_RECIPIENT.to_string(),
+            amount: None,
+            spl_token: spl_token.map(|mint| mint.to_string()),
+            decimals,
+            token_program: None,
+            memo: Some("order 7".to_string()),
+            message: None,
+            references: Vec::new(),
+        }
+    }
+
+    fn assert_references_on_transfer(instructions: &[Instruction], transfer_program: &Pubkey, references: &[Pubkey]) {
+        let transfer_instruction = instructions.last().unwrap();
+        assert_eq!(transfer_instruction.program_id, *transfer_program);
+        let reference_metas = &transfer_instruction.accounts[transfer_instruction.accounts.len() - references.len()..];
+        for (account_meta, reference) in reference_metas.iter().zip(references) {
+            assert_eq!(account_meta, &AccountMeta::new_readonly(*reference, false));
+        }
+        let memo_instruction = &instructions[instructions.len() - 2];
+        assert_eq!(memo_instruction.program_id, spl_memo::ID);
+        assert!(memo_instruction.accounts.iter().all(|account_meta| !references.contains(&account_meta.pubkey)));
+    }
+
+    #[test]
+    fn sol_template_references_are_read_only_transfer_accounts() {
+        let payer = Pubkey::new_unique();
+        let references = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let instructions = build_template_instructions(&pay_template(None, None), &payer, "0.5", &references).unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_references_on_transfer(&instructions, &solana_sdk::system_program::ID, &references);
+        assert_eq!(instructions[1].accounts.len(), 2 + references.len());
+        assert!(build_template_instructions(&pay_template(None, None), &payer, "0.0000000001", &references).is_err());
+    }
+
+    #[test]
+    fn spl_template_references_are_read_only_transfer_accounts() {
+        let payer = Pubkey::new_unique();
+        let references = [Pubkey::new_unique()];
+        let template = pay_template(Some(Pubkey::new_unique()), Some(6));
+        let instructions = build_template_instructions(&template, &payer, "2.5", &references).unwrap();
+        assert_eq!(instructions[0].program_id, spl_associated_token_account::ID);
+        assert_references_on_transfer(&instructions, &spl_token::ID, &references);
+        assert!(build_template_instructions(&template, &payer, "2.5000001", &references).is_err());
+    }
+
+    #[test]
+    fn renders_qr_codes() {
+        let transfer_url = format!("solana:{}", SPEC_RECIPIENT);
+        let svg_code = render_qr_code(&transfer_url, "svg").unwrap();
+        assert!(svg_code.data.contains("<svg"));
+        let png_code = render_qr_code(&transfer_url, "png").unwrap();
+        let png_bytes = general_purpose::STANDARD.decode(png_code.data).unwrap();
+        assert_eq!(&png_bytes[..8], b"\x89PNG\r\n\x1a\n");
+        assert!(render_qr_code(&transfer_url, "gif").is_err());
+    }
+}
@@ -0,0 +1,456 @@
+use axum::{extract::Json, response::IntoResponse};
+use serde_json::json;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::stake::instruction as stake_instruction;
+use solana_sdk::stake::state::{Authorized, Lockup, StakeAuthorize, StakeStateV2};
+use crate::handlers::compute_budget::build_budgeted_response;
+use crate::handlers::sol::rent_exempt_lamports;
+use crate::models::request::{
+    ComputeBudgeted, StakeAuthorizeInput, StakeCreationInput, StakeDeactivationInput, StakeDelegationInput,
+    StakeLockupInput, StakeMergeInput, StakeSplitInput, StakeWithdrawInput,
+};
+use crate::models::response::InstructionOutput;
+use crate::utils::validation::*;
+
+pub fn build_stake_creation_instructions(input: &StakeCreationInput) -> Result<Vec<Instruction>, String> {
+    let funding_address = match input.from {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let stake_address = match input.stake_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let stake_lamports = match input.lamports {
+        Some(amount) => {
+            validate_amount_bounds(amount, "lamports")?;
+            amount
+        },
+        None => return Err("Missing required fields".to_string()),
+    };
+    let minimum_lamports = rent_exempt_lamports(StakeStateV2::size_of() as u64);
+    if stake_lamports < minimum_lamports {
+        return Err(format!(
+            "Invalid lamports - stake accounts need at least {} for rent exemption",
+            minimum_lamports
+        ));
+    }
+    let parsed_funding = validate_pubkey_format(funding_address, "from")?;
+    let parsed_stake = validate_pubkey_format(stake_address, "stake account")?;
+    if parsed_funding == parsed_stake {
+        return Err("Funding account and stake account cannot be the same".to_string());
+    }
+    let authorized = Authorized {
+        staker: parse_optional_pubkey(input.staker.as_deref(), "staker")?.unwrap_or(parsed_funding),
+        withdrawer: parse_optional_pubkey(input.withdrawer.as_deref(), "withdrawer")?.unwrap_or(parsed_funding),
+    };
+    let lockup = match input.lockup {
+        Some(ref lockup) => parse_stake_lockup(lockup)?,
+        None => Lockup::default(),
+    };
+    Ok(stake_instruction::create_account(
+        &parsed_funding,
+        &parsed_stake,
+        &authorized,
+        &lockup,
+        stake_lamports,
+    ))
+}
+
+pub fn build_stake_delegation_instruction(input: &StakeDelegationInput) -> Result<Instruction, String> {
+    let stake_address = match input.stake_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let vote_address = match input.vote_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let staker_address = match input.staker {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_stake = validate_pubkey_format(stake_address, "stake account")?;
+    let parsed_vote = validate_pubkey_format(vote_address, "vote account")?;
+    let parsed_staker = validate_pubkey_format(staker_address, "staker")?;
+    Ok(stake_instruction::delegate_stake(&parsed_stake, &parsed_staker, &parsed_vote))
+}
+
+pub fn build_stake_deactivation_instruction(input: &StakeDeactivationInput) -> Result<Instruction, String> {
+    let stake_address = match input.stake_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let staker_address = match input.staker {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_stake = validate_pubkey_format(stake_address, "stake account")?;
+    let parsed_staker = validate_pubkey_format(staker_address, "staker")?;
+    Ok(stake_instruction::deactivate_stake(&parsed_stake, &parsed_staker))
+}
+
+pub fn build_stake_withdraw_instruction(input: &StakeWithdrawInput) -> Result<Instruction, String> {
+    let stake_address = match input.stake_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let withdrawer_address = match input.withdrawer {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let destination_address = match input.to {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let withdraw_lamports = match input.lamports {
+        Some(amount) => {
+            validate_amount_bounds(amount, "lamports")?;
+            amount
+        },
+        None => return Err("Missing required fields".to_string()),
+    };
+    let parsed_stake = validate_pubkey_format(stake_address, "stake account")?;
+    let parsed_withdrawer = validate_pubkey_format(withdrawer_address, "withdrawer")?;
+    let parsed_destination = validate_pubkey_format(destination_address, "to")?;
+    let parsed_custodian = parse_optional_pubkey(input.custodian.as_deref(), "custodian")?;
+    if parsed_stake == parsed_destination {
+        return Err("Cannot withdraw to the stake account itself".to_string());
+    }
+    Ok(stake_instruction::withdraw(
+        &parsed_stake,
+        &parsed_withdrawer,
+        &parsed_destination,
+        withdraw_lamports,
+        parsed_custodian.as_ref(),
+    ))
+}
+
+pub fn build_stake_split_instructions(input: &StakeSplitInput) -> Result<Vec<Instruction>, String> {
+    let stake_address = match input.stake_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let staker_address = match input.staker {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let split_address = match input.split_stake_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let split_lamports = match input.lamports {
+        Some(amount) => {
+            validate_amount_bounds(amount, "lamports")?;
+            amount
+        },
+        None => return Err("Missing required fields".to_string()),
+    };
+    let parsed_stake = validate_pubkey_format(stake_address, "stake account")?;
+    let parsed_staker = validate_pubkey_format(staker_address, "staker")?;
+    let parsed_split = validate_pubkey_format(split_address, "split stake account")?;
+    if parsed_stake == parsed_split {
+        return Err("Split stake account cannot be the same as the source stake account".to_string());
+    }
+    Ok(stake_instruction::split(&parsed_stake, &parsed_staker, split_lamports, &parsed_split))
+}
+
+pub fn build_stake_merge_instructions(input: &StakeMergeInput) -> Result<Vec<Instruction>, String> {
+    let destination_address = match input.destination_stake_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let source_address = match input.source_stake_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let staker_address = match input.staker {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_destination = validate_pubkey_format(destination_address, "destination stake account")?;
+    let parsed_source = validate_pubkey_format(source_address, "source stake account")?;
+    let parsed_staker = validate_pubkey_format(staker_address, "staker")?;
+    if parsed_destination == parsed_source {
+        return Err("Cannot merge a stake account into itself".to_string());
+    }
+    Ok(stake_instruction::merge(&parsed_destination, &parsed_source, &parsed_staker))
+}
+
+pub fn build_stake_authorize_instruction(input: &StakeAuthorizeInput) -> Result<Instruction, String> {
+    let stake_address = match input.stake_account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let authority_address = match input.authority {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let new_authority_address = match input.new_authority {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let stake_authorize = match input.authority_type.as_deref().map(str::trim) {
+        Some("staker") => StakeAuthorize::Staker,
+        Some("withdrawer") => StakeAuthorize::Withdrawer,
+        Some(other) if !other.is_empty() => {
+            return Err(format!("Unsupported stake authority type: {} - expected staker or withdrawer", other));
+        },
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let parsed_stake = validate_pubkey_format(stake_address, "stake account")?;
+    let parsed_authority = validate_pubkey_format(authority_address, "authority")?;
+    let parsed_new_authority = validate_pubkey_format(new_authority_address, "new authority")?;
+    let parsed_custodian = parse_optional_pubkey(input.custodian.as_deref(), "custodian")?;
+    Ok(stake_instruction::authorize(
+        &parsed_stake,
+        &parsed_authority,
+        &parsed_new_authority,
+        stake_authorize,
+        parsed_custodian.as_ref(),
+    ))
+}
+
+fn parse_stake_lockup(lockup: &StakeLockupInput) -> Result<Lockup, String> {
+    Ok(Lockup {
+        unix_timestamp: lockup.unix_timestamp.unwrap_or_default(),
+        epoch: lockup.epoch.unwrap_or_default(),
+        custodian: parse_optional_pubkey(lockup.custodian.as_deref(), "custodian")?.unwrap_or_default(),
+    })
+}
+
+pub async fn handle_stake_creation(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<StakeCreationInput>>,
+) -> impl IntoResponse {
+    match build_stake_creation_instructions(&input) {
+        Ok(stake_instructions) => build_budgeted_response((), stake_instructions, &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_stake_delegation(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<StakeDelegationInput>>,
+) -> impl IntoResponse {
+    match build_stake_delegation_instruction(&input) {
+        Ok(delegate_instruction) => build_budgeted_response(
+            InstructionOutput::from(&delegate_instruction),
+            vec![delegate_instruction],
+            &compute_budget,
+        ),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_stake_deactivation(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<StakeDeactivationInput>>,
+) -> impl IntoResponse {
+    match build_stake_deactivation_instruction(&input) {
+        Ok(deactivate_instruction) => build_budgeted_response(
+            InstructionOutput::from(&deactivate_instruction),
+            vec![deactivate_instruction],
+            &compute_budget,
+        ),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_stake_withdraw(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<StakeWithdrawInput>>,
+) -> impl IntoResponse {
+    match build_stake_withdraw_instruction(&input) {
+        Ok(withdraw_instruction) => build_budgeted_response(
+            InstructionOutput::from(&withdraw_instruction),
+            vec![withdraw_instruction],
+            &compute_budget,
+        ),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_stake_split(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<StakeSplitInput>>,
+) -> impl IntoResponse {
+    match build_stake_split_instructions(&input) {
+        Ok(split_instructions) => build_budgeted_response((), split_instructions, &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_stake_merge(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<StakeMergeInput>>,
+) -> impl IntoResponse {
+    match build_stake_merge_instructions(&input) {
+        Ok(merge_instructions) => build_budgeted_response((), merge_instructions, &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_stake_authorize(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<StakeAuthorizeInput>>,
+) -> impl IntoResponse {
+    match build_stake_authorize_instruction(&input) {
+        Ok(authorize_instruction) => build_budgeted_response(
+            InstructionOutput::from(&authorize_instruction),
+            vec![authorize_instruction],
+            &compute_budget,
+        ),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use solana_sdk::program_utils::limited_deserialize;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_sdk::stake::instruction::StakeInstruction;
+    use solana_sdk::stake::program as stake_program;
+    use solana_sdk::system_instruction::SystemInstruction;
+    use solana_sdk::system_program;
+
+    fn input<T: DeserializeOwned>(value: serde_json::Value) -> T {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn decode_stake(instruction: &Instruction) -> StakeInstruction {
+        assert_eq!(instruction.program_id, stake_program::ID);
+        limited_deserialize(&instruction.data).unwrap()
+    }
+
+    fn decode_system(instruction: &Instruction) -> SystemInstruction {
+        assert_eq!(instruction.program_id, system_program::ID);
+        limited_deserialize(&instruction.data).unwrap()
+    }
+
+    #[test]
+    fn creation_sets_authorities_and_lockup() {
+        let (from, stake_account, staker, custodian) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let lamports = rent_exempt_lamports(StakeStateV2::size_of() as u64) + 1_000_000_000;
+        let stake_instructions = build_stake_creation_instructions(&input(json!({
+            "from": from.to_string(),
+            "stake_account": stake_account.to_string(),
+            "staker": staker.to_string(),
+            "lockup": { "unix_timestamp": 1_700_000_000, "epoch": 500, "custodian": custodian.to_string() },
+            "lamports": lamports,
+        })))
+        .unwrap();
+        assert_eq!(stake_instructions.len(), 2);
+        assert_eq!(decode_system(&stake_instructions[0]), SystemInstruction::CreateAccount {
+            lamports,
+            space: StakeStateV2::size_of() as u64,
+            owner: stake_program::ID,
+        });
+        assert_eq!(stake_instructions[1].accounts[0].pubkey, stake_account);
+        assert_eq!(decode_stake(&stake_instructions[1]), StakeInstruction::Initialize(
+            Authorized { staker, withdrawer: from },
+            Lockup { unix_timestamp: 1_700_000_000, epoch: 500, custodian },
+        ));
+    }
+
+    #[test]
+    fn creation_defaults_authorities_to_funder_without_lockup() {
+        let (from, stake_account) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let minimum_lamports = rent_exempt_lamports(StakeStateV2::size_of() as u64);
+        let stake_instructions = build_stake_creation_instructions(&input(json!({
+            "from": from.to_string(),
+            "stake_account": stake_account.to_string(),
+            "lamports": minimum_lamports,
+        })))
+        .unwrap();
+        assert_eq!(
+            decode_stake(&stake_instructions[1]),
+            StakeInstruction::Initialize(Authorized::auto(&from), Lockup::default())
+        );
+
+        let underfunded = build_stake_creation_instructions(&input(json!({
+            "from": from.to_string(),
+            "stake_account": stake_account.to_string(),
+            "lamports": minimum_lamports - 1,
+        })));
+        assert!(underfunded.unwrap_err().contains("rent exemption"));
+        let self_funded = build_stake_creation_instructions(&input(json!({
+            "from": from.to_string(),
+            "stake_account": from.to_string(),
+            "lamports": minimum_lamports,
+        })));
+        assert_eq!(self_funded.unwrap_err(), "Funding account and stake account cannot be the same");
+    }
+
+    #[test]
+    fn split_allocates_assigns_and_splits() {
+        let (stake_account, staker, split_account) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let split_instructions = build_stake_split_instructions(&input(json!({
+            "stake_account": stake_account.to_string(),
+            "staker": staker.to_string(),
+            "split_stake_account": split_account.to_string(),
+            "lamports": 2_000_000_000u64,
+        })))
+        .unwrap();
+        assert_eq!(split_instructions.len(), 3);
+        assert_eq!(split_instructions[0].accounts[0].pubkey, split_account);
+        assert_eq!(
+            decode_system(&split_instructions[0]),
+            SystemInstruction::Allocate { space: StakeStateV2::size_of() as u64 }
+        );
+        assert_eq!(split_instructions[1].accounts[0].pubkey, split_account);
+        assert_eq!(decode_system(&split_instructions[1]), SystemInstruction::Assign { owner: stake_program::ID });
+        let split_keys: Vec<Pubkey> = split_instructions[2].accounts.iter().map(|account| account.pubkey).collect();
+        assert_eq!(split_keys, [stake_account, split_account, staker]);
+        assert_eq!(decode_stake(&split_instructions[2]), StakeInstruction::Split(2_000_000_000));
+
+        let split_into_self = build_stake_split_instructions(&input(json!({
+            "stake_account": stake_account.to_string(),
+            "staker": staker.to_string(),
+            "split_stake_account": stake_account.to_string(),
+            "lamports": 1,
+        })));
+        assert_eq!(split_into_self.unwrap_err(), "Split stake account cannot be the same as the source stake account");
+    }
+
+    #[test]
+    fn delegate_withdraw_and_authorize() {
+        let (stake_account, staker, vote_account) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let delegate = build_stake_delegation_instruction(&input(json!({
+            "stake_account": stake_account.to_string(),
+            "vote_account": vote_account.to_string(),
+            "staker": staker.to_string(),
+        })))
+        .unwrap();
+        assert_eq!(delegate.accounts[1].pubkey, vote_account);
+        assert_eq!(decode_stake(&delegate), StakeInstruction::DelegateStake);
+
+        let destination = Pubkey::new_unique();
+        let withdraw = build_stake_withdraw_instruction(&input(json!({
+            "stake_account": stake_account.to_string(),
+            "withdrawer": staker.to_string(),
+            "to": destination.to_string(),
+            "lamports": 10,
+        })))
+        .unwrap();
+        assert_eq!(withdraw.accounts[1].pubkey, destination);
+        assert_eq!(decode_stake(&withdraw), StakeInstruction::Withdraw(10));
+
+        let new_authority = Pubkey::new_unique();
+        let authorize = build_stake_authorize_instruction(&input(json!({
+            "stake_account": stake_account.to_string(),
+            "authority": staker.to_string(),
+            "new_authority": new_authority.to_string(),
+            "authority_type": "withdrawer",
+        })))
+        .unwrap();
+        assert_eq!(decode_stake(&authorize), StakeInstruction::Authorize(new_authority, StakeAuthorize::Withdrawer));
+        let unknown_type = build_stake_authorize_instruction(&input(json!({
+            "stake_account": stake_account.to_string(),
+            "authority": staker.to_string(),
+            "new_authority": new_authority.to_string(),
+            "authority_type": "voter",
+        })));
+        assert!(unknown_type.unwrap_err().starts_with("Unsupported stake authority type: voter"));
+    }
+}
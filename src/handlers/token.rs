@@ -1,182 +1,820 @@
 use axum::{extract::Json, response::IntoResponse};
 use base64::{engine::general_purpose, Engine as _};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_pack::Pack;
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::Keypair;
-use spl_token::{instruction as token_instructions, ID as SPL_TOKEN_PROGRAM};
-use spl_associated_token_account;
-use crate::models::request::{TokenCreationInput, TokenMintingInput, TokenTransferInput};
-use crate::models::response::{InstructionOutput, AccountMetadata, TokenAccountMetadata, TokenTransferOutput};
+use solana_sdk::rent::Rent;
+use solana_sdk::system_instruction;
+use spl_token::instruction as token_instructions;
+use spl_token_2022::extension::interest_bearing_mint::instruction as interest_bearing_instructions;
+use spl_token_2022::extension::transfer_fee::instruction as transfer_fee_instructions;
+use spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
+use spl_token_2022::extension::{ExtensionType, StateWithExtensions};
+use spl_token_2022::state::{Mint, Multisig};
+use spl_token_2022::{instruction as token_2022_instructions, ID as SPL_TOKEN_2022_PROGRAM};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use crate::handlers::sol::build_sol_transfer_instruction;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token_2022::instruction::{AuthorityType, MAX_SIGNERS};
+use crate::handlers::compute_budget::build_budgeted_response;
+use crate::handlers::memo::attach_memo;
+use crate::models::request::{
+    AssociatedTokenAccountInput, ComputeBudgeted, MintExtensionInput, SolTransferInput, TokenApproveInput, TokenBurnInput,
+    TokenCloseInput, TokenCreationInput, TokenFreezeInput, TokenMintingInput, TokenMultisigInput, TokenRevokeInput,
+    TokenSetAuthorityInput, TokenTransferInput, UnwrapSolInput, WrapSolInput,
+};
+use crate::models::response::{
+    AssociatedTokenAccountOutput, InstructionOutput, MintCreationOutput, TokenAccountMetadata,
+    TokenTransferOutput,
+};
 use crate::utils::validation::*;
 use serde_json::json;
 
-pub async fn handle_token_creation(Json(input): Json<TokenCreationInput>) -> impl IntoResponse {
+pub fn build_token_creation_instruction(input: &TokenCreationInput) -> Result<Instruction, String> {
     let authority_address = match input.mint_authority {
         Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+        _ => return Err("Missing required fields".to_string()),
     };
     let mint_address = match input.mint {
         Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+        _ => return Err("Missing required fields".to_string()),
     };
     let token_decimals = match input.decimals {
         Some(d) => d,
-        None => return build_error_response("Missing required fields"),
-    };
-    if let Err(error_msg) = validate_token_decimals(token_decimals) {
-        return build_error_response(&error_msg);
-    }
-    let parsed_authority = match validate_pubkey_format(authority_address, "mint authority") {
-        Ok(addr) => addr,
-        Err(error_msg) => return build_error_response(&error_msg),
-    };
-    let parsed_mint = match validate_pubkey_format(mint_address, "mint") {
-        Ok(addr) => addr,
-        Err(error_msg) => return build_error_response(&error_msg),
+        None => return Err("Missing required fields".to_string()),
     };
+    validate_token_decimals(token_decimals)?;
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_authority = validate_pubkey_format(authority_address, "mint authority")?;
+    let parsed_mint = validate_pubkey_format(mint_address, "mint")?;
     if parsed_authority == parsed_mint {
-        return build_error_response("Mint and mint authority cannot be the same");
+        return Err("Mint and mint authority cannot be the same".to_string());
     }
-    let token_instruction = match token_instructions::initialize_mint(
-        &SPL_TOKEN_PROGRAM,
+    token_2022_instructions::initialize_mint(
+        &token_program,
         &parsed_mint,
         &parsed_authority,
         None,
         token_decimals,
-    ) {
-        Ok(instruction) => instruction,
-        Err(error) => return build_error_response(&format!("Failed to create token instruction: {}", error)),
+    )
+    .map_err(|error| format!("Failed to create token instruction: {}", error))
+}
+
+pub struct TokenCreation {
+    pub instructions: Vec<Instruction>,
+    pub account_space: Option<usize>,
+}
+
+pub fn build_token_creation_instructions(input: &TokenCreationInput) -> Result<TokenCreation, String> {
+    let initialize_instruction = build_token_creation_instruction(input)?;
+    let mint_extensions = match input.extensions {
+        Some(ref extensions) if !extensions.is_empty() => extensions,
+        _ => {
+            return Ok(TokenCreation {
+                instructions: vec![initialize_instruction],
+                account_space: None,
+            })
+        },
     };
-    let account_list: Vec<AccountMetadata> = token_instruction.accounts
-        .iter()
-        .map(|account| AccountMetadata {
-            pubkey: account.pubkey.to_string(),
-            is_signer: account.is_signer,
-            is_writable: account.is_writable,
-        })
-        .collect();
-    let encoded_instruction_data = general_purpose::STANDARD.encode(&token_instruction.data);
-    let creation_result = InstructionOutput {
-        program_id: token_instruction.program_id.to_string(),
-        accounts: account_list,
-        instruction_data: encoded_instruction_data,
+    let token_program = initialize_instruction.program_id;
+    if token_program != SPL_TOKEN_2022_PROGRAM {
+        return Err("Mint extensions require token_program token-2022".to_string());
+    }
+    let payer_address = match input.payer {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
     };
-    build_success_response(creation_result)
+    let parsed_payer = validate_pubkey_format(payer_address, "payer")?;
+    let parsed_mint = validate_pubkey_format(input.mint.as_deref().unwrap_or_default(), "mint")?;
+    if parsed_payer == parsed_mint {
+        return Err("Payer and mint cannot be the same".to_string());
+    }
+    let account_space = mint_account_space(mint_extensions)?;
+    let mut instructions = vec![system_instruction::create_account(
+        &parsed_payer,
+        &parsed_mint,
+        Rent::default().minimum_balance(account_space),
+        account_space as u64,
+        &token_program,
+    )];
+    for extension in mint_extensions {
+        instructions.push(build_mint_extension_instruction(&token_program, &parsed_mint, extension)?);
+    }
+    instructions.push(initialize_instruction);
+    Ok(TokenCreation {
+        instructions,
+        account_space: Some(account_space),
+    })
 }
 
-pub async fn handle_token_minting(Json(input): Json<TokenMintingInput>) -> impl IntoResponse {
-    let mint_address = match input.mint {
-        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+fn mint_account_space(extensions: &[MintExtensionInput]) -> Result<usize, String> {
+    let mut extension_types: Vec<ExtensionType> = Vec::with_capacity(extensions.len());
+    for extension in extensions {
+        let extension_type = match extension {
+            MintExtensionInput::TransferFee { .. } => ExtensionType::TransferFeeConfig,
+            MintExtensionInput::PermanentDelegate { .. } => ExtensionType::PermanentDelegate,
+            MintExtensionInput::NonTransferable => ExtensionType::NonTransferable,
+            MintExtensionInput::InterestBearing { .. } => ExtensionType::InterestBearingConfig,
+        };
+        if extension_types.contains(&extension_type) {
+            return Err(format!("Duplicate mint extension: {:?}", extension_type));
+        }
+        extension_types.push(extension_type);
+    }
+    ExtensionType::try_calculate_account_len::<Mint>(&extension_types)
+        .map_err(|error| format!("Failed to calculate mint account size: {}", error))
+}
+
+fn build_mint_extension_instruction(
+    token_program: &Pubkey,
+    mint: &Pubkey,
+    extension: &MintExtensionInput,
+) -> Result<Instruction, String> {
+    let extension_instruction = match extension {
+        MintExtensionInput::TransferFee {
+            transfer_fee_config_authority,
+            withdraw_withheld_authority,
+            transfer_fee_basis_points,
+            maximum_fee,
+        } => {
+            let basis_points = match transfer_fee_basis_points {
+                Some(points) if *points <= MAX_FEE_BASIS_POINTS => *points,
+                Some(_) => return Err(format!("Invalid transfer fee - maximum is {} basis points", MAX_FEE_BASIS_POINTS)),
+                None => return Err("Missing required fields".to_string()),
+            };
+            let maximum_fee = maximum_fee.ok_or_else(|| "Missing required fields".to_string())?;
+            let config_authority = parse_optional_pubkey(transfer_fee_config_authority.as_deref(), "transfer fee config authority")?;
+            let withdraw_authority = parse_optional_pubkey(withdraw_withheld_authority.as_deref(), "withdraw withheld authority")?;
+            transfer_fee_instructions::initialize_transfer_fee_config(
+                token_program,
+                mint,
+                config_authority.as_ref(),
+                withdraw_authority.as_ref(),
+                basis_points,
+                maximum_fee,
+            )
+        },
+        MintExtensionInput::PermanentDelegate { delegate } => {
+            let delegate_address = match delegate {
+                Some(addr) if !addr.trim().is_empty() => addr.trim(),
+                _ => return Err("Missing required fields".to_string()),
+            };
+            let parsed_delegate = validate_pubkey_format(delegate_address, "permanent delegate")?;
+            token_2022_instructions::initialize_permanent_delegate(token_program, mint, &parsed_delegate)
+        },
+        MintExtensionInput::NonTransferable => {
+            token_2022_instructions::initialize_non_transferable_mint(token_program, mint)
+        },
+        MintExtensionInput::InterestBearing { rate_authority, rate } => {
+            let interest_rate = rate.ok_or_else(|| "Missing required fields".to_string())?;
+            let parsed_rate_authority = parse_optional_pubkey(rate_authority.as_deref(), "rate authority")?;
+            interest_bearing_instructions::initialize(token_program, mint, parsed_rate_authority, interest_rate)
+        },
     };
-    let destination_address = match input.destination {
+    extension_instruction.map_err(|error| format!("Failed to create extension instruction: {}", error))
+}
+
+pub fn build_token_minting_instruction(input: &TokenMintingInput) -> Result<Instruction, String> {
+    let mint_address = match input.mint {
         Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+        _ => return Err("Missing required fields".to_string()),
     };
     let authority_address = match input.authority {
         Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+        _ => return Err("Missing required fields".to_string()),
     };
     let mint_amount = match input.amount {
         Some(amount) => {
-            if let Err(error_msg) = validate_amount_bounds(amount, "amount") {
-                return build_error_response(&error_msg);
-            }
+            validate_amount_bounds(amount, "amount")?;
             amount
         },
-        None => return build_error_response("Missing required fields"),
+        None => return Err("Missing required fields".to_string()),
     };
-    let parsed_mint = match validate_pubkey_format(mint_address, "mint") {
-        Ok(addr) => addr,
-        Err(error_msg) => return build_error_response(&error_msg),
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_mint = validate_pubkey_format(mint_address, "mint")?;
+    let parsed_destination = resolve_token_destination(
+        input.destination.as_deref(),
+        input.destination_owner.as_deref(),
+        &parsed_mint,
+        &token_program,
+    )?;
+    let parsed_authority = validate_pubkey_format(authority_address, "authority")?;
+    let multisig_signers = parse_multisig_signers(input.multisig_signers.as_deref())?;
+    let signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    if parsed_destination == parsed_mint {
+        return Err("Destination cannot be the same as mint address".to_string());
+    }
+    let mint_instruction = match checked_decimals(&token_program, input.decimals)? {
+        Some(decimals) => token_2022_instructions::mint_to_checked(
+            &token_program,
+            &parsed_mint,
+            &parsed_destination,
+            &parsed_authority,
+            &signer_refs,
+            mint_amount,
+            decimals,
+        ),
+        None => token_instructions::mint_to(
+            &token_program,
+            &parsed_mint,
+            &parsed_destination,
+            &parsed_authority,
+            &signer_refs,
+            mint_amount,
+        ),
     };
-    let parsed_destination = match validate_pubkey_format(destination_address, "destination") {
-        Ok(addr) => addr,
-        Err(error_msg) => return build_error_response(&error_msg),
+    mint_instruction.map_err(|error| format!("Failed to create mint instruction: {}", error))
+}
+
+pub fn build_token_transfer_instruction(input: &TokenTransferInput) -> Result<Instruction, String> {
+    let mint_address = match input.mint {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
     };
-    let parsed_authority = match validate_pubkey_format(authority_address, "authority") {
-        Ok(addr) => addr,
-        Err(error_msg) => return build_error_response(&error_msg),
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
     };
-    if parsed_destination == parsed_mint {
-        return build_error_response("Destination cannot be the same as mint address");
-    }
-    let mint_instruction = match token_instructions::mint_to(
-        &SPL_TOKEN_PROGRAM,
-        &parsed_mint,
-        &parsed_destination,
-        &parsed_authority,
-        &[],
-        mint_amount,
-    ) {
-        Ok(instruction) => instruction,
-        Err(error) => return build_error_response(&format!("Failed to create mint instruction: {}", error)),
+    let transfer_decimals = resolve_transfer_decimals(input)?;
+    let transfer_amount = match (input.amount, input.ui_amount.as_deref()) {
+        (Some(_), Some(_)) => return Err("Provide either amount or ui_amount, not both".to_string()),
+        (Some(amount), None) => amount,
+        (None, Some(ui_amount)) => {
+            let decimals = transfer_decimals
+                .ok_or_else(|| "ui_amount requires decimals or mint_account_data".to_string())?;
+            parse_ui_amount(ui_amount, decimals)?
+        },
+        (None, None) => return Err("Missing required fields".to_string()),
     };
-    let account_list: Vec<AccountMetadata> = mint_instruction.accounts
-        .iter()
-        .map(|account| AccountMetadata {
-            pubkey: account.pubkey.to_string(),
-            is_signer: account.is_signer,
-            is_writable: account.is_writable,
-        })
-        .collect();
-    let encoded_instruction_data = general_purpose::STANDARD.encode(&mint_instruction.data);
-    let minting_result = InstructionOutput {
-        program_id: mint_instruction.program_id.to_string(),
-        accounts: account_list,
-        instruction_data: encoded_instruction_data,
+    validate_amount_bounds(transfer_amount, "amount")?;
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let checked_transfer_decimals = match input.checked {
+        Some(true) if transfer_decimals.is_none() => {
+            return Err("Checked transfers require decimals or mint_account_data".to_string());
+        },
+        Some(false) if token_program == SPL_TOKEN_2022_PROGRAM => {
+            return Err("Token-2022 transfers must be checked".to_string());
+        },
+        Some(false) => {
+            if let Some(decimals) = transfer_decimals {
+                validate_token_decimals(decimals)?;
+            }
+            None
+        },
+        _ => checked_decimals(&token_program, transfer_decimals)?,
     };
-    build_success_response(minting_result)
+    let parsed_mint = validate_pubkey_format(mint_address, "mint")?;
+    let parsed_destination = resolve_token_destination(
+        input.destination.as_deref(),
+        input.destination_owner.as_deref(),
+        &parsed_mint,
+        &token_program,
+    )?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let multisig_signers = parse_multisig_signers(input.multisig_signers.as_deref())?;
+    let signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let source_token_account = get_associated_token_address_with_program_id(
+        &parsed_owner,
+        &parsed_mint,
+        &token_program,
+    );
+    if source_token_account == parsed_destination {
+        return Err("Cannot transfer to the same token account".to_string());
+    }
+    let transfer_instruction = match checked_transfer_decimals {
+        Some(decimals) => token_2022_instructions::transfer_checked(
+            &token_program,
+            &source_token_account,
+            &parsed_mint,
+            &parsed_destination,
+            &parsed_owner,
+            &signer_refs,
+            transfer_amount,
+            decimals,
+        ),
+        None => token_instructions::transfer(
+            &token_program,
+            &source_token_account,
+            &parsed_destination,
+            &parsed_owner,
+            &signer_refs,
+            transfer_amount,
+        ),
+    };
+    transfer_instruction.map_err(|error| format!("Failed to create transfer instruction: {}", error))
 }
 
-pub async fn handle_token_transfer(Json(input): Json<TokenTransferInput>) -> impl IntoResponse {
-    let destination_address = match input.destination {
+pub fn build_token_minting_instructions(input: &TokenMintingInput) -> Result<Vec<Instruction>, String> {
+    let mint_instruction = build_token_minting_instruction(input)?;
+    prepend_destination_ata(
+        mint_instruction,
+        input.destination_owner.as_deref(),
+        input.payer.as_deref().or(input.authority.as_deref()),
+        input.mint.as_deref(),
+    )
+}
+
+pub fn build_token_transfer_instructions(input: &TokenTransferInput) -> Result<Vec<Instruction>, String> {
+    let transfer_instruction = build_token_transfer_instruction(input)?;
+    let transfer_instructions = prepend_destination_ata(
+        transfer_instruction,
+        input.destination_owner.as_deref(),
+        input.payer.as_deref().or(input.owner.as_deref()),
+        input.mint.as_deref(),
+    )?;
+    attach_memo(transfer_instructions, input.memo.as_deref())
+}
+
+pub fn build_associated_token_account_instruction(input: &AssociatedTokenAccountInput) -> Result<Instruction, String> {
+    let owner_address = match input.owner {
         Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+        _ => return Err("Missing required fields".to_string()),
     };
     let mint_address = match input.mint {
         Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let parsed_mint = validate_pubkey_format(mint_address, "mint")?;
+    let parsed_payer = parse_optional_pubkey(input.payer.as_deref(), "payer")?.unwrap_or(parsed_owner);
+    Ok(create_associated_token_account_idempotent(
+        &parsed_payer,
+        &parsed_owner,
+        &parsed_mint,
+        &token_program,
+    ))
+}
+
+pub fn build_token_burn_instruction(input: &TokenBurnInput) -> Result<Instruction, String> {
+    let mint_address = match input.mint {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
     };
     let owner_address = match input.owner {
         Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
-        _ => return build_error_response("Missing required fields"),
+        _ => return Err("Missing required fields".to_string()),
     };
-    let transfer_amount = match input.amount {
+    let burn_amount = match input.amount {
         Some(amount) => {
-            if let Err(error_msg) = validate_amount_bounds(amount, "amount") {
-                return build_error_response(&error_msg);
-            }
+            validate_amount_bounds(amount, "amount")?;
             amount
         },
-        None => return build_error_response("Missing required fields"),
+        None => return Err("Missing required fields".to_string()),
     };
-    let parsed_destination = match validate_pubkey_format(destination_address, "destination") {
-        Ok(addr) => addr,
-        Err(error_msg) => return build_error_response(&error_msg),
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_mint = validate_pubkey_format(mint_address, "mint")?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let multisig_signers = parse_multisig_signers(input.multisig_signers.as_deref())?;
+    let signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let token_account = resolve_token_account(
+        input.account.as_deref(),
+        Some(&parsed_owner),
+        Some(&parsed_mint),
+        &token_program,
+    )?;
+    let burn_instruction = match input.decimals {
+        Some(decimals) => {
+            validate_token_decimals(decimals)?;
+            token_2022_instructions::burn_checked(
+                &token_program,
+                &token_account,
+                &parsed_mint,
+                &parsed_owner,
+                &signer_refs,
+                burn_amount,
+                decimals,
+            )
+        },
+        None => token_2022_instructions::burn(
+            &token_program,
+            &token_account,
+            &parsed_mint,
+            &parsed_owner,
+            &signer_refs,
+            burn_amount,
+        ),
+    };
+    burn_instruction.map_err(|error| format!("Failed to create burn instruction: {}", error))
+}
+
+pub fn build_token_approve_instruction(input: &TokenApproveInput) -> Result<Instruction, String> {
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let delegate_address = match input.delegate {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let approve_amount = match input.amount {
+        Some(amount) => {
+            validate_amount_bounds(amount, "amount")?;
+            amount
+        },
+        None => return Err("Missing required fields".to_string()),
+    };
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_mint = parse_optional_pubkey(input.mint.as_deref(), "mint")?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let parsed_delegate = validate_pubkey_format(delegate_address, "delegate")?;
+    if parsed_delegate == parsed_owner {
+        return Err("Delegate cannot be the same as owner".to_string());
+    }
+    let token_account = resolve_token_account(
+        input.account.as_deref(),
+        Some(&parsed_owner),
+        parsed_mint.as_ref(),
+        &token_program,
+    )?;
+    let approve_instruction = match (input.decimals, parsed_mint) {
+        (Some(decimals), Some(mint)) => {
+            validate_token_decimals(decimals)?;
+            token_2022_instructions::approve_checked(
+                &token_program,
+                &token_account,
+                &mint,
+                &parsed_delegate,
+                &parsed_owner,
+                &[],
+                approve_amount,
+                decimals,
+            )
+        },
+        (Some(_), None) => return Err("Checked approvals require mint".to_string()),
+        (None, _) => token_2022_instructions::approve(
+            &token_program,
+            &token_account,
+            &parsed_delegate,
+            &parsed_owner,
+            &[],
+            approve_amount,
+        ),
+    };
+    approve_instruction.map_err(|error| format!("Failed to create approve instruction: {}", error))
+}
+
+pub fn build_token_revoke_instruction(input: &TokenRevokeInput) -> Result<Instruction, String> {
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_mint = parse_optional_pubkey(input.mint.as_deref(), "mint")?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let token_account = resolve_token_account(
+        input.account.as_deref(),
+        Some(&parsed_owner),
+        parsed_mint.as_ref(),
+        &token_program,
+    )?;
+    token_2022_instructions::revoke(&token_program, &token_account, &parsed_owner, &[])
+        .map_err(|error| format!("Failed to create revoke instruction: {}", error))
+}
+
+pub fn build_token_freeze_instruction(input: &TokenFreezeInput) -> Result<Instruction, String> {
+    build_freeze_state_instruction(input, true)
+}
+
+pub fn build_token_thaw_instruction(input: &TokenFreezeInput) -> Result<Instruction, String> {
+    build_freeze_state_instruction(input, false)
+}
+
+fn build_freeze_state_instruction(input: &TokenFreezeInput, freeze: bool) -> Result<Instruction, String> {
+    let mint_address = match input.mint {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let authority_address = match input.freeze_authority {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_mint = validate_pubkey_format(mint_address, "mint")?;
+    let parsed_authority = validate_pubkey_format(authority_address, "freeze authority")?;
+    let multisig_signers = parse_multisig_signers(input.multisig_signers.as_deref())?;
+    let signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let parsed_owner = parse_optional_pubkey(input.owner.as_deref(), "owner")?;
+    let token_account = resolve_token_account(
+        input.account.as_deref(),
+        parsed_owner.as_ref(),
+        Some(&parsed_mint),
+        &token_program,
+    )?;
+    let freeze_instruction = if freeze {
+        token_2022_instructions::freeze_account(&token_program, &token_account, &parsed_mint, &parsed_authority, &signer_refs)
+    } else {
+        token_2022_instructions::thaw_account(&token_program, &token_account, &parsed_mint, &parsed_authority, &signer_refs)
+    };
+    freeze_instruction.map_err(|error| format!("Failed to create freeze instruction: {}", error))
+}
+
+pub fn build_token_close_instruction(input: &TokenCloseInput) -> Result<Instruction, String> {
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_mint = parse_optional_pubkey(input.mint.as_deref(), "mint")?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let parsed_destination = parse_optional_pubkey(input.destination.as_deref(), "destination")?.unwrap_or(parsed_owner);
+    let token_account = resolve_token_account(
+        input.account.as_deref(),
+        Some(&parsed_owner),
+        parsed_mint.as_ref(),
+        &token_program,
+    )?;
+    if token_account == parsed_destination {
+        return Err("Destination cannot be the account being closed".to_string());
+    }
+    token_2022_instructions::close_account(&token_program, &token_account, &parsed_destination, &parsed_owner, &[])
+        .map_err(|error| format!("Failed to create close instruction: {}", error))
+}
+
+pub fn build_token_set_authority_instruction(input: &TokenSetAuthorityInput) -> Result<Instruction, String> {
+    let account_address = match input.account {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let authority_address = match input.current_authority {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let authority_type = match input.authority_type {
+        Some(ref name) if !name.trim().is_empty() => parse_authority_type(name.trim())?,
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let is_legacy_authority = matches!(
+        authority_type,
+        AuthorityType::MintTokens | AuthorityType::FreezeAccount | AuthorityType::AccountOwner | AuthorityType::CloseAccount
+    );
+    if !is_legacy_authority && token_program != SPL_TOKEN_2022_PROGRAM {
+        return Err("Extension authority types require token_program token-2022".to_string());
+    }
+    let parsed_account = validate_pubkey_format(account_address, "account")?;
+    let parsed_authority = validate_pubkey_format(authority_address, "current authority")?;
+    let multisig_signers = parse_multisig_signers(input.multisig_signers.as_deref())?;
+    let signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let parsed_new_authority = parse_optional_pubkey(input.new_authority.as_deref(), "new authority")?;
+    token_2022_instructions::set_authority(
+        &token_program,
+        &parsed_account,
+        parsed_new_authority.as_ref(),
+        authority_type,
+        &parsed_authority,
+        &signer_refs,
+    )
+    .map_err(|error| format!("Failed to create set authority instruction: {}", error))
+}
+
+pub fn build_multisig_creation_instructions(input: &TokenMultisigInput) -> Result<Vec<Instruction>, String> {
+    let multisig_address = match input.multisig {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let signer_threshold = match input.threshold {
+        Some(threshold) => threshold,
+        None => return Err("Missing required fields".to_string()),
+    };
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_multisig = validate_pubkey_format(multisig_address, "multisig")?;
+    let multisig_signers = parse_multisig_signers(input.signers.as_deref())?;
+    if multisig_signers.is_empty() {
+        return Err("Missing required fields".to_string());
+    }
+    if signer_threshold == 0 || signer_threshold as usize > multisig_signers.len() {
+        return Err(format!(
+            "Invalid threshold - must be between 1 and {} signers",
+            multisig_signers.len()
+        ));
+    }
+    if multisig_signers.contains(&parsed_multisig) {
+        return Err("Multisig account cannot be one of its signers".to_string());
+    }
+    let signer_refs: Vec<&Pubkey> = multisig_signers.iter().collect();
+    let initialize_instruction = token_2022_instructions::initialize_multisig(
+        &token_program,
+        &parsed_multisig,
+        &signer_refs,
+        signer_threshold,
+    )
+    .map_err(|error| format!("Failed to create multisig instruction: {}", error))?;
+    let parsed_payer = match parse_optional_pubkey(input.payer.as_deref(), "payer")? {
+        Some(payer) => payer,
+        None => return Ok(vec![initialize_instruction]),
+    };
+    if parsed_payer == parsed_multisig {
+        return Err("Payer and multisig cannot be the same".to_string());
+    }
+    let create_instruction = system_instruction::create_account(
+        &parsed_payer,
+        &parsed_multisig,
+        Rent::default().minimum_balance(Multisig::LEN),
+        Multisig::LEN as u64,
+        &token_program,
+    );
+    Ok(vec![create_instruction, initialize_instruction])
+}
+
+fn parse_multisig_signers(signers: Option<&[String]>) -> Result<Vec<Pubkey>, String> {
+    let signer_addresses = match signers {
+        Some(addresses) => addresses,
+        None => return Ok(Vec::new()),
+    };
+    if signer_addresses.len() > MAX_SIGNERS {
+        return Err(format!("Too many multisig signers - maximum is {}", MAX_SIGNERS));
+    }
+    let mut parsed_signers: Vec<Pubkey> = Vec::with_capacity(signer_addresses.len());
+    for address in signer_addresses {
+        let parsed_signer = validate_pubkey_format(address.trim(), "multisig signer")?;
+        if parsed_signers.contains(&parsed_signer) {
+            return Err(format!("Duplicate multisig signer: {}", parsed_signer));
+        }
+        parsed_signers.push(parsed_signer);
+    }
+    Ok(parsed_signers)
+}
+
+pub fn build_wrap_sol_instructions(input: &WrapSolInput) -> Result<Vec<Instruction>, String> {
+    let owner_address = match input.owner {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return Err("Missing required fields".to_string()),
+    };
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let parsed_owner = validate_pubkey_format(owner_address, "owner")?;
+    let parsed_payer = parse_optional_pubkey(input.payer.as_deref(), "payer")?.unwrap_or(parsed_owner);
+    let native_mint = native_mint_for(&token_program);
+    let wrapped_account = get_associated_token_address_with_program_id(&parsed_owner, &native_mint, &token_program);
+    let transfer_instruction = build_sol_transfer_instruction(&SolTransferInput {
+        from: Some(parsed_owner.to_string()),
+        to: Some(wrapped_account.to_string()),
+        lamports: input.lamports,
+        memo: None,
+    })?;
+    let sync_instruction = token_2022_instructions::sync_native(&token_program, &wrapped_account)
+        .map_err(|error| format!("Failed to create sync native instruction: {}", error))?;
+    Ok(vec![
+        create_associated_token_account_idempotent(&parsed_payer, &parsed_owner, &native_mint, &token_program),
+        transfer_instruction,
+        sync_instruction,
+    ])
+}
+
+pub fn build_unwrap_sol_instructions(input: &UnwrapSolInput) -> Result<Vec<Instruction>, String> {
+    let token_program = validate_token_program(input.token_program.as_deref())?;
+    let close_instruction = build_token_close_instruction(&TokenCloseInput {
+        account: input.account.clone(),
+        mint: Some(native_mint_for(&token_program).to_string()),
+        owner: input.owner.clone(),
+        destination: input.destination.clone(),
+        token_program: Some(token_program.to_string()),
+    })?;
+    Ok(vec![close_instruction])
+}
+
+fn native_mint_for(token_program: &Pubkey) -> Pubkey {
+    if *token_program == SPL_TOKEN_2022_PROGRAM {
+        spl_token_2022::native_mint::ID
+    } else {
+        spl_token::native_mint::ID
+    }
+}
+
+fn resolve_token_account(
+    account: Option<&str>,
+    owner: Option<&Pubkey>,
+    mint: Option<&Pubkey>,
+    token_program: &Pubkey,
+) -> Result<Pubkey, String> {
+    match (parse_optional_pubkey(account, "account")?, owner, mint) {
+        (Some(token_account), _, _) => Ok(token_account),
+        (None, Some(owner), Some(mint)) => Ok(get_associated_token_address_with_program_id(owner, mint, token_program)),
+        _ => Err("Missing required fields".to_string()),
+    }
+}
+
+fn resolve_token_destination(
+    destination: Option<&str>,
+    destination_owner: Option<&str>,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<Pubkey, String> {
+    let parsed_destination = parse_optional_pubkey(destination, "destination")?;
+    let parsed_destination_owner = parse_optional_pubkey(destination_owner, "destination owner")?;
+    match (parsed_destination, parsed_destination_owner) {
+        (Some(_), Some(_)) => Err("Provide either destination or destination_owner, not both".to_string()),
+        (Some(account), None) => Ok(account),
+        (None, Some(owner)) => Ok(get_associated_token_address_with_program_id(&owner, mint, token_program)),
+        (None, None) => Err("Missing required fields".to_string()),
+    }
+}
+
+fn prepend_destination_ata(
+    token_instruction: Instruction,
+    destination_owner: Option<&str>,
+    payer: Option<&str>,
+    mint: Option<&str>,
+) -> Result<Vec<Instruction>, String> {
+    let parsed_destination_owner = match parse_optional_pubkey(destination_owner, "destination owner")? {
+        Some(owner) => owner,
+        None => return Ok(vec![token_instruction]),
+    };
+    let parsed_payer = validate_pubkey_format(payer.unwrap_or_default().trim(), "payer")?;
+    let parsed_mint = validate_pubkey_format(mint.unwrap_or_default().trim(), "mint")?;
+    let create_instruction = create_associated_token_account_idempotent(
+        &parsed_payer,
+        &parsed_destination_owner,
+        &parsed_mint,
+        &token_instruction.program_id,
+    );
+    Ok(vec![create_instruction, token_instruction])
+}
+
+fn resolve_transfer_decimals(input: &TokenTransferInput) -> Result<Option<u8>, String> {
+    let account_decimals = match input.mint_account_data {
+        Some(ref data) if !data.trim().is_empty() => Some(decode_mint_decimals(data.trim())?),
+        _ => None,
     };
-    let parsed_mint = match validate_pubkey_format(mint_address, "mint") {
-        Ok(addr) => addr,
+    match (input.decimals, account_decimals) {
+        (Some(given), Some(decoded)) if given != decoded => Err(format!(
+            "Decimals mismatch - request says {} but mint account data says {}",
+            given, decoded
+        )),
+        (Some(given), _) => Ok(Some(given)),
+        (None, decoded) => Ok(decoded),
+    }
+}
+
+fn decode_mint_decimals(encoded_data: &str) -> Result<u8, String> {
+    let account_data = general_purpose::STANDARD
+        .decode(encoded_data)
+        .map_err(|_| "Invalid mint account data - expected base64".to_string())?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&account_data)
+        .map_err(|_| "Invalid mint account data".to_string())?;
+    Ok(mint_state.base.decimals)
+}
+
+fn checked_decimals(token_program: &Pubkey, decimals: Option<u8>) -> Result<Option<u8>, String> {
+    match decimals {
+        Some(d) => {
+            validate_token_decimals(d)?;
+            Ok(Some(d))
+        },
+        None if *token_program == SPL_TOKEN_2022_PROGRAM => Err("Token-2022 requires decimals".to_string()),
+        None => Ok(None),
+    }
+}
+
+pub async fn handle_token_creation(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenCreationInput>>,
+) -> impl IntoResponse {
+    let token_creation = match build_token_creation_instructions(&input) {
+        Ok(creation) => creation,
         Err(error_msg) => return build_error_response(&error_msg),
     };
-    let parsed_owner = match validate_pubkey_format(owner_address, "owner") {
-        Ok(addr) => addr,
+    let creation_instructions = token_creation.instructions;
+    let account_space = match token_creation.account_space {
+        Some(space) => space,
+        None => {
+            let initialize_output = InstructionOutput::from(&creation_instructions[0]);
+            return build_budgeted_response(initialize_output, creation_instructions, &compute_budget);
+        },
+    };
+    let creation_output = MintCreationOutput {
+        space: account_space,
+        lamports: Rent::default().minimum_balance(account_space),
+    };
+    build_budgeted_response(creation_output, creation_instructions, &compute_budget)
+}
+
+pub async fn handle_token_minting(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenMintingInput>>,
+) -> impl IntoResponse {
+    let mint_instructions = match build_token_minting_instructions(&input) {
+        Ok(instructions) => instructions,
         Err(error_msg) => return build_error_response(&error_msg),
     };
-    let source_token_account = spl_associated_token_account::get_associated_token_address(&parsed_owner, &parsed_mint);
-    if source_token_account == parsed_destination {
-        return build_error_response("Cannot transfer to the same token account");
+    match mint_instructions.as_slice() {
+        [mint_instruction] => {
+            let mint_output = InstructionOutput::from(mint_instruction);
+            build_budgeted_response(mint_output, mint_instructions, &compute_budget)
+        },
+        _ => build_budgeted_response((), mint_instructions, &compute_budget),
     }
-    let transfer_instruction = match token_instructions::transfer(
-        &SPL_TOKEN_PROGRAM,
-        &source_token_account,
-        &parsed_destination,
-        &parsed_owner,
-        &[],
-        transfer_amount,
-    ) {
-        Ok(instruction) => instruction,
-        Err(error) => return build_error_response(&format!("Failed to create transfer instruction: {}", error)),
+}
+
+pub async fn handle_token_transfer(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenTransferInput>>,
+) -> impl IntoResponse {
+    let transfer_instructions = match build_token_transfer_instructions(&input) {
+        Ok(instructions) => instructions,
+        Err(error_msg) => return build_error_response(&error_msg),
     };
+    if transfer_instructions.len() > 1 {
+        return build_budgeted_response((), transfer_instructions, &compute_budget);
+    }
+    let transfer_instruction = &transfer_instructions[0];
     let account_list: Vec<TokenAccountMetadata> = transfer_instruction.accounts
         .iter()
         .map(|account| TokenAccountMetadata {
@@ -190,13 +828,627 @@ pub async fn handle_token_transfer(Json(input): Json<TokenTransferInput>) -> imp
         accounts: account_list,
         instruction_data: encoded_instruction_data,
     };
-    build_success_response(transfer_result)
+    build_budgeted_response(transfer_result, transfer_instructions, &compute_budget)
+}
+
+pub async fn handle_associated_token_account(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<AssociatedTokenAccountInput>>,
+) -> impl IntoResponse {
+    let create_instruction = match build_associated_token_account_instruction(&input) {
+        Ok(instruction) => instruction,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let ata_output = AssociatedTokenAccountOutput {
+        address: create_instruction.accounts[1].pubkey.to_string(),
+        instruction: InstructionOutput::from(&create_instruction),
+    };
+    build_budgeted_response(ata_output, vec![create_instruction], &compute_budget)
+}
+
+pub async fn handle_token_burn(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenBurnInput>>,
+) -> impl IntoResponse {
+    match build_token_burn_instruction(&input) {
+        Ok(burn_instruction) => build_budgeted_response(InstructionOutput::from(&burn_instruction), vec![burn_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_token_approve(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenApproveInput>>,
+) -> impl IntoResponse {
+    match build_token_approve_instruction(&input) {
+        Ok(approve_instruction) => build_budgeted_response(InstructionOutput::from(&approve_instruction), vec![approve_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_token_revoke(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenRevokeInput>>,
+) -> impl IntoResponse {
+    match build_token_revoke_instruction(&input) {
+        Ok(revoke_instruction) => build_budgeted_response(InstructionOutput::from(&revoke_instruction), vec![revoke_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
 }
 
-fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
-    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+pub async fn handle_token_freeze(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenFreezeInput>>,
+) -> impl IntoResponse {
+    match build_token_freeze_instruction(&input) {
+        Ok(freeze_instruction) => build_budgeted_response(InstructionOutput::from(&freeze_instruction), vec![freeze_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_token_thaw(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenFreezeInput>>,
+) -> impl IntoResponse {
+    match build_token_thaw_instruction(&input) {
+        Ok(thaw_instruction) => build_budgeted_response(InstructionOutput::from(&thaw_instruction), vec![thaw_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_token_close(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenCloseInput>>,
+) -> impl IntoResponse {
+    match build_token_close_instruction(&input) {
+        Ok(close_instruction) => build_budgeted_response(InstructionOutput::from(&close_instruction), vec![close_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_token_set_authority(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenSetAuthorityInput>>,
+) -> impl IntoResponse {
+    match build_token_set_authority_instruction(&input) {
+        Ok(authority_instruction) => build_budgeted_response(InstructionOutput::from(&authority_instruction), vec![authority_instruction], &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_multisig_creation(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<TokenMultisigInput>>,
+) -> impl IntoResponse {
+    let multisig_instructions = match build_multisig_creation_instructions(&input) {
+        Ok(instructions) => instructions,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    match multisig_instructions.as_slice() {
+        [initialize_instruction] => {
+            let multisig_output = InstructionOutput::from(initialize_instruction);
+            build_budgeted_response(multisig_output, multisig_instructions, &compute_budget)
+        },
+        _ => build_budgeted_response((), multisig_instructions, &compute_budget),
+    }
+}
+
+pub async fn handle_sol_wrap(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<WrapSolInput>>,
+) -> impl IntoResponse {
+    match build_wrap_sol_instructions(&input) {
+        Ok(wrap_instructions) => build_budgeted_response((), wrap_instructions, &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub async fn handle_sol_unwrap(
+    Json(ComputeBudgeted { request: input, compute_budget }): Json<ComputeBudgeted<UnwrapSolInput>>,
+) -> impl IntoResponse {
+    match build_unwrap_sol_instructions(&input) {
+        Ok(unwrap_instructions) => build_budgeted_response((), unwrap_instructions, &compute_budget),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
 }
 
 fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
     (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use solana_sdk::program_option::COption;
+    use spl_token_2022::instruction::TokenInstruction;
+    use spl_token::instruction::TokenInstruction as LegacyTokenInstruction;
+
+    fn input<T: DeserializeOwned>(value: serde_json::Value) -> T {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn account_keys(instruction: &Instruction) -> Vec<Pubkey> {
+        instruction.accounts.iter().map(|account| account.pubkey).collect()
+    }
+
+    fn decode_legacy(instruction: &Instruction) -> LegacyTokenInstruction<'_> {
+        assert_eq!(instruction.program_id, spl_token::ID);
+        LegacyTokenInstruction::unpack(&instruction.data).unwrap()
+    }
+
+    fn decode_token_2022(instruction: &Instruction) -> TokenInstruction<'_> {
+        assert_eq!(instruction.program_id, SPL_TOKEN_2022_PROGRAM);
+        TokenInstruction::unpack(&instruction.data).unwrap()
+    }
+
+    fn encoded_mint_data(decimals: u8) -> String {
+        let mut mint_data = vec![0u8; spl_token::state::Mint::LEN];
+        let mint_state = spl_token::state::Mint {
+            mint_authority: COption::Some(Pubkey::new_unique()),
+            decimals,
+            is_initialized: true,
+            ..spl_token::state::Mint::default()
+        };
+        spl_token::state::Mint::pack(mint_state, &mut mint_data).unwrap();
+        general_purpose::STANDARD.encode(mint_data)
+    }
+
+    fn transfer_input(owner: &Pubkey, mint: &Pubkey, destination: &Pubkey, fields: serde_json::Value) -> TokenTransferInput {
+        let mut request = json!({
+            "owner": owner.to_string(),
+            "mint": mint.to_string(),
+            "destination": destination.to_string(),
+        });
+        request.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        input(request)
+    }
+
+    #[test]
+    fn transfer_checked_for_both_programs() {
+        let (owner, mint, destination) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+        let legacy = build_token_transfer_instructions(&transfer_input(&owner, &mint, &destination, json!({
+            "amount": 1_500,
+            "decimals": 6,
+        })))
+        .unwrap();
+        assert_eq!(legacy.len(), 1);
+        let legacy_source = get_associated_token_address_with_program_id(&owner, &mint, &spl_token::ID);
+        assert_eq!(account_keys(&legacy[0]), [legacy_source, mint, destination, owner]);
+        assert!(legacy[0].accounts[3].is_signer);
+        assert_eq!(decode_legacy(&legacy[0]), LegacyTokenInstruction::TransferChecked { amount: 1_500, decimals: 6 });
+
+        let token_2022 = build_token_transfer_instructions(&transfer_input(&owner, &mint, &destination, json!({
+            "amount": "2500",
+            "decimals": 9,
+            "token_program": "token-2022",
+        })))
+        .unwrap();
+        let token_2022_source = get_associated_token_address_with_program_id(&owner, &mint, &SPL_TOKEN_2022_PROGRAM);
+        assert_ne!(token_2022_source, legacy_source);
+        assert_eq!(account_keys(&token_2022[0]), [token_2022_source, mint, destination, owner]);
+        assert_eq!(decode_token_2022(&token_2022[0]), TokenInstruction::TransferChecked { amount: 2_500, decimals: 9 });
+    }
+
+    #[test]
+    fn transfer_honours_unchecked_flag() {
+        let (owner, mint, destination) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let legacy = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "amount": 7,
+            "decimals": 6,
+            "checked": false,
+        })))
+        .unwrap();
+        let source = get_associated_token_address_with_program_id(&owner, &mint, &spl_token::ID);
+        assert_eq!(account_keys(&legacy), [source, destination, owner]);
+        assert_eq!(decode_legacy(&legacy), LegacyTokenInstruction::Transfer { amount: 7 });
+
+        let unchecked_token_2022 = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "amount": 7,
+            "decimals": 6,
+            "checked": false,
+            "token_program": "token-2022",
+        })));
+        assert_eq!(unchecked_token_2022.unwrap_err(), "Token-2022 transfers must be checked");
+        let missing_decimals = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "amount": 7,
+            "token_program": "token-2022",
+        })));
+        assert_eq!(missing_decimals.unwrap_err(), "Token-2022 requires decimals");
+        let checked_without_decimals = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "amount": 7,
+            "checked": true,
+        })));
+        assert_eq!(checked_without_decimals.unwrap_err(), "Checked transfers require decimals or mint_account_data");
+    }
+
+    #[test]
+    fn transfer_converts_ui_amount_to_base_units() {
+        let (owner, mint, destination) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let transfer = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "ui_amount": "1.5",
+            "decimals": 6,
+        })))
+        .unwrap();
+        assert_eq!(decode_legacy(&transfer), LegacyTokenInstruction::TransferChecked { amount: 1_500_000, decimals: 6 });
+
+        let without_decimals = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "ui_amount": "1.5",
+        })));
+        assert_eq!(without_decimals.unwrap_err(), "ui_amount requires decimals or mint_account_data");
+        let both_amounts = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "amount": 1,
+            "ui_amount": "1",
+            "decimals": 6,
+        })));
+        assert_eq!(both_amounts.unwrap_err(), "Provide either amount or ui_amount, not both");
+    }
+
+    #[test]
+    fn transfer_resolves_decimals_from_mint_account_data() {
+        let (owner, mint, destination) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let transfer = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "ui_amount": "2.25",
+            "mint_account_data": encoded_mint_data(4),
+            "token_program": "token-2022",
+        })))
+        .unwrap();
+        assert_eq!(decode_token_2022(&transfer), TokenInstruction::TransferChecked { amount: 22_500, decimals: 4 });
+
+        let mismatch = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "amount": 1,
+            "decimals": 6,
+            "mint_account_data": encoded_mint_data(4),
+        })));
+        assert_eq!(mismatch.unwrap_err(), "Decimals mismatch - request says 6 but mint account data says 4");
+        let garbage = build_token_transfer_instruction(&transfer_input(&owner, &mint, &destination, json!({
+            "amount": 1,
+            "mint_account_data": general_purpose::STANDARD.encode([1u8; 10]),
+        })));
+        assert_eq!(garbage.unwrap_err(), "Invalid mint account data");
+    }
+
+    #[test]
+    fn transfer_to_destination_owner_creates_ata() {
+        let (owner, mint, recipient, payer) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        for token_program in [spl_token::ID, SPL_TOKEN_2022_PROGRAM] {
+            let transfer_instructions = build_token_transfer_instructions(&input(json!({
+                "owner": owner.to_string(),
+                "mint": mint.to_string(),
+                "destination_owner": recipient.to_string(),
+                "payer": payer.to_string(),
+                "amount": 10,
+                "decimals": 0,
+                "token_program": token_program.to_string(),
+                "memo": "invoice 42",
+            })))
+            .unwrap();
+            assert_eq!(transfer_instructions.len(), 3);
+            assert_eq!(
+                transfer_instructions[0],
+                create_associated_token_account_idempotent(&payer, &recipient, &mint, &token_program)
+            );
+            assert_eq!(transfer_instructions[1].program_id, spl_memo::ID);
+            assert_eq!(transfer_instructions[1].data, b"invoice 42");
+            let recipient_account = get_associated_token_address_with_program_id(&recipient, &mint, &token_program);
+            assert_eq!(transfer_instructions[2].program_id, token_program);
+            assert_eq!(transfer_instructions[2].accounts[2].pubkey, recipient_account);
+        }
+    }
+
+    #[test]
+    fn transfer_rejects_same_account() {
+        let (owner, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let own_account = get_associated_token_address_with_program_id(&owner, &mint, &spl_token::ID);
+        let to_self = build_token_transfer_instruction(&transfer_input(&owner, &mint, &own_account, json!({ "amount": 1, "decimals": 0 })));
+        assert_eq!(to_self.unwrap_err(), "Cannot transfer to the same token account");
+        let to_own_wallet = build_token_transfer_instruction(&input(json!({
+            "owner": owner.to_string(),
+            "mint": mint.to_string(),
+            "destination_owner": owner.to_string(),
+            "amount": 1,
+            "decimals": 0,
+        })));
+        assert_eq!(to_own_wallet.unwrap_err(), "Cannot transfer to the same token account");
+        let both_destinations = build_token_transfer_instruction(&transfer_input(&owner, &mint, &Pubkey::new_unique(), json!({
+            "destination_owner": Pubkey::new_unique().to_string(),
+            "amount": 1,
+        })));
+        assert_eq!(both_destinations.unwrap_err(), "Provide either destination or destination_owner, not both");
+    }
+
+    #[test]
+    fn transfer_appends_multisig_signers() {
+        let (multisig, mint, destination) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let (first_signer, second_signer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let transfer = build_token_transfer_instruction(&transfer_input(&multisig, &mint, &destination, json!({
+            "amount": 5,
+            "decimals": 2,
+            "multisig_signers": [first_signer.to_string(), second_signer.to_string()],
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&transfer)[3..], [multisig, first_signer, second_signer]);
+        assert!(!transfer.accounts[3].is_signer);
+        assert!(transfer.accounts[4].is_signer && transfer.accounts[5].is_signer);
+
+        let duplicate = build_token_transfer_instruction(&transfer_input(&multisig, &mint, &destination, json!({
+            "amount": 5,
+            "decimals": 2,
+            "multisig_signers": [first_signer.to_string(), first_signer.to_string()],
+        })));
+        assert_eq!(duplicate.unwrap_err(), format!("Duplicate multisig signer: {}", first_signer));
+        let too_many: Vec<String> = (0..=MAX_SIGNERS).map(|_| Pubkey::new_unique().to_string()).collect();
+        let oversized = build_token_transfer_instruction(&transfer_input(&multisig, &mint, &destination, json!({
+            "amount": 5,
+            "decimals": 2,
+            "multisig_signers": too_many,
+        })));
+        assert_eq!(oversized.unwrap_err(), format!("Too many multisig signers - maximum is {}", MAX_SIGNERS));
+    }
+
+    #[test]
+    fn multisig_creation_allocates_and_initializes() {
+        let (multisig, payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let signer_strings: Vec<String> = signers.iter().map(Pubkey::to_string).collect();
+        for token_program in [spl_token::ID, SPL_TOKEN_2022_PROGRAM] {
+            let multisig_instructions = build_multisig_creation_instructions(&input(json!({
+                "multisig": multisig.to_string(),
+                "signers": signer_strings,
+                "threshold": 2,
+                "payer": payer.to_string(),
+                "token_program": token_program.to_string(),
+            })))
+            .unwrap();
+            assert_eq!(
+                multisig_instructions[0],
+                system_instruction::create_account(
+                    &payer,
+                    &multisig,
+                    Rent::default().minimum_balance(Multisig::LEN),
+                    Multisig::LEN as u64,
+                    &token_program,
+                )
+            );
+            assert_eq!(multisig_instructions[1].program_id, token_program);
+            assert_eq!(account_keys(&multisig_instructions[1])[2..], signers[..]);
+            assert_eq!(TokenInstruction::unpack(&multisig_instructions[1].data).unwrap(), TokenInstruction::InitializeMultisig { m: 2 });
+        }
+
+        for threshold in [0, 4] {
+            let invalid_threshold = build_multisig_creation_instructions(&input(json!({
+                "multisig": multisig.to_string(),
+                "signers": signer_strings,
+                "threshold": threshold,
+            })));
+            assert_eq!(invalid_threshold.unwrap_err(), "Invalid threshold - must be between 1 and 3 signers");
+        }
+        let self_signer = build_multisig_creation_instructions(&input(json!({
+            "multisig": multisig.to_string(),
+            "signers": [multisig.to_string()],
+            "threshold": 1,
+        })));
+        assert_eq!(self_signer.unwrap_err(), "Multisig account cannot be one of its signers");
+    }
+
+    #[test]
+    fn wrap_and_unwrap_use_program_native_mint() {
+        let owner = Pubkey::new_unique();
+        for (token_program, native_mint) in [
+            (spl_token::ID, spl_token::native_mint::ID),
+            (SPL_TOKEN_2022_PROGRAM, spl_token_2022::native_mint::ID),
+        ] {
+            let wrapped_account = get_associated_token_address_with_program_id(&owner, &native_mint, &token_program);
+            let wrap_instructions = build_wrap_sol_instructions(&input(json!({
+                "owner": owner.to_string(),
+                "lamports": 1_000_000,
+                "token_program": token_program.to_string(),
+            })))
+            .unwrap();
+            assert_eq!(wrap_instructions, [
+                create_associated_token_account_idempotent(&owner, &owner, &native_mint, &token_program),
+                system_instruction::transfer(&owner, &wrapped_account, 1_000_000),
+                token_2022_instructions::sync_native(&token_program, &wrapped_account).unwrap(),
+            ]);
+
+            let unwrap_instructions = build_unwrap_sol_instructions(&input(json!({
+                "owner": owner.to_string(),
+                "token_program": token_program.to_string(),
+            })))
+            .unwrap();
+            assert_eq!(unwrap_instructions.len(), 1);
+            assert_eq!(unwrap_instructions[0].program_id, token_program);
+            assert_eq!(account_keys(&unwrap_instructions[0]), [wrapped_account, owner, owner]);
+            assert_eq!(TokenInstruction::unpack(&unwrap_instructions[0].data).unwrap(), TokenInstruction::CloseAccount);
+        }
+    }
+
+    #[test]
+    fn burn_approve_and_revoke() {
+        let (owner, mint, delegate) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let owner_account = get_associated_token_address_with_program_id(&owner, &mint, &spl_token::ID);
+        let burn = build_token_burn_instruction(&input(json!({
+            "owner": owner.to_string(),
+            "mint": mint.to_string(),
+            "amount": 30,
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&burn), [owner_account, mint, owner]);
+        assert_eq!(decode_legacy(&burn), LegacyTokenInstruction::Burn { amount: 30 });
+        let burn_checked = build_token_burn_instruction(&input(json!({
+            "owner": owner.to_string(),
+            "mint": mint.to_string(),
+            "amount": 30,
+            "decimals": 3,
+            "token_program": "token-2022",
+        })))
+        .unwrap();
+        assert_eq!(decode_token_2022(&burn_checked), TokenInstruction::BurnChecked { amount: 30, decimals: 3 });
+
+        let approve = build_token_approve_instruction(&input(json!({
+            "owner": owner.to_string(),
+            "mint": mint.to_string(),
+            "delegate": delegate.to_string(),
+            "amount": 99,
+            "decimals": 2,
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&approve), [owner_account, mint, delegate, owner]);
+        assert_eq!(decode_legacy(&approve), LegacyTokenInstruction::ApproveChecked { amount: 99, decimals: 2 });
+        let approve_without_mint = build_token_approve_instruction(&input(json!({
+            "owner": owner.to_string(),
+            "account": owner_account.to_string(),
+            "delegate": delegate.to_string(),
+            "amount": 99,
+            "decimals": 2,
+        })));
+        assert_eq!(approve_without_mint.unwrap_err(), "Checked approvals require mint");
+        let self_delegate = build_token_approve_instruction(&input(json!({
+            "owner": owner.to_string(),
+            "mint": mint.to_string(),
+            "delegate": owner.to_string(),
+            "amount": 99,
+        })));
+        assert_eq!(self_delegate.unwrap_err(), "Delegate cannot be the same as owner");
+
+        let revoke = build_token_revoke_instruction(&input(json!({
+            "owner": owner.to_string(),
+            "mint": mint.to_string(),
+            "token_program": "token-2022",
+        })))
+        .unwrap();
+        let token_2022_account = get_associated_token_address_with_program_id(&owner, &mint, &SPL_TOKEN_2022_PROGRAM);
+        assert_eq!(account_keys(&revoke), [token_2022_account, owner]);
+        assert_eq!(decode_token_2022(&revoke), TokenInstruction::Revoke);
+    }
+
+    #[test]
+    fn freeze_thaw_and_close() {
+        let (owner, mint, freeze_authority) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let freeze_input: TokenFreezeInput = input(json!({
+            "owner": owner.to_string(),
+            "mint": mint.to_string(),
+            "freeze_authority": freeze_authority.to_string(),
+        }));
+        let owner_account = get_associated_token_address_with_program_id(&owner, &mint, &spl_token::ID);
+        let freeze = build_token_freeze_instruction(&freeze_input).unwrap();
+        assert_eq!(account_keys(&freeze), [owner_account, mint, freeze_authority]);
+        assert_eq!(decode_legacy(&freeze), LegacyTokenInstruction::FreezeAccount);
+        let thaw = build_token_thaw_instruction(&freeze_input).unwrap();
+        assert_eq!(account_keys(&thaw), [owner_account, mint, freeze_authority]);
+        assert_eq!(decode_legacy(&thaw), LegacyTokenInstruction::ThawAccount);
+
+        let close = build_token_close_instruction(&input(json!({
+            "owner": owner.to_string(),
+            "mint": mint.to_string(),
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&close), [owner_account, owner, owner]);
+        assert_eq!(decode_legacy(&close), LegacyTokenInstruction::CloseAccount);
+        let close_into_itself = build_token_close_instruction(&input(json!({
+            "owner": owner.to_string(),
+            "account": owner_account.to_string(),
+            "destination": owner_account.to_string(),
+        })));
+        assert_eq!(close_into_itself.unwrap_err(), "Destination cannot be the account being closed");
+    }
+
+    #[test]
+    fn set_authority_gates_extension_types_to_token_2022() {
+        let (mint, current_authority, new_authority) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let legacy = build_token_set_authority_instruction(&input(json!({
+            "account": mint.to_string(),
+            "authority_type": "mint_tokens",
+            "current_authority": current_authority.to_string(),
+            "new_authority": new_authority.to_string(),
+        })))
+        .unwrap();
+        assert_eq!(account_keys(&legacy), [mint, current_authority]);
+        assert_eq!(decode_legacy(&legacy), LegacyTokenInstruction::SetAuthority {
+            authority_type: spl_token::instruction::AuthorityType::MintTokens,
+            new_authority: COption::Some(new_authority),
+        });
+
+        let extension_on_legacy = build_token_set_authority_instruction(&input(json!({
+            "account": mint.to_string(),
+            "authority_type": "transfer_fee_config",
+            "current_authority": current_authority.to_string(),
+        })));
+        assert_eq!(extension_on_legacy.unwrap_err(), "Extension authority types require token_program token-2022");
+        let token_2022 = build_token_set_authority_instruction(&input(json!({
+            "account": mint.to_string(),
+            "authority_type": "transfer_fee_config",
+            "current_authority": current_authority.to_string(),
+            "token_program": "token-2022",
+        })))
+        .unwrap();
+        assert_eq!(decode_token_2022(&token_2022), TokenInstruction::SetAuthority {
+            authority_type: AuthorityType::TransferFeeConfig,
+            new_authority: COption::None,
+        });
+        let unknown = build_token_set_authority_instruction(&input(json!({
+            "account": mint.to_string(),
+            "authority_type": "mint_everything",
+            "current_authority": current_authority.to_string(),
+        })));
+        assert_eq!(unknown.unwrap_err(), "Unsupported authority type: mint_everything");
+    }
+
+    #[test]
+    fn minting_to_destination_owner_creates_ata() {
+        let (mint, authority, recipient) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mint_instructions = build_token_minting_instructions(&input(json!({
+            "mint": mint.to_string(),
+            "authority": authority.to_string(),
+            "destination_owner": recipient.to_string(),
+            "amount": 1_000,
+            "decimals": 3,
+            "token_program": "token-2022",
+        })))
+        .unwrap();
+        let recipient_account = get_associated_token_address_with_program_id(&recipient, &mint, &SPL_TOKEN_2022_PROGRAM);
+        assert_eq!(
+            mint_instructions[0],
+            create_associated_token_account_idempotent(&authority, &recipient, &mint, &SPL_TOKEN_2022_PROGRAM)
+        );
+        assert_eq!(account_keys(&mint_instructions[1]), [mint, recipient_account, authority]);
+        assert_eq!(decode_token_2022(&mint_instructions[1]), TokenInstruction::MintToChecked { amount: 1_000, decimals: 3 });
+    }
+
+    #[test]
+    fn token_creation_sizes_extension_mints() {
+        let (mint, authority, payer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let plain = build_token_creation_instructions(&input(json!({
+            "mintAuthority": authority.to_string(),
+            "mint": mint.to_string(),
+            "decimals": 6,
+        })))
+        .unwrap();
+        assert_eq!(plain.account_space, None);
+        assert_eq!(plain.instructions.len(), 1);
+
+        let with_extensions = build_token_creation_instructions(&input(json!({
+            "mintAuthority": authority.to_string(),
+            "mint": mint.to_string(),
+            "decimals": 6,
+            "payer": payer.to_string(),
+            "token_program": "token-2022",
+            "extensions": [{ "type": "non_transferable" }],
+        })))
+        .unwrap();
+        let expected_space = ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::NonTransferable]).unwrap();
+        assert_eq!(with_extensions.account_space, Some(expected_space));
+        assert_eq!(
+            with_extensions.instructions[0],
+            system_instruction::create_account(
+                &payer,
+                &mint,
+                Rent::default().minimum_balance(expected_space),
+                expected_space as u64,
+                &SPL_TOKEN_2022_PROGRAM,
+            )
+        );
+        assert_eq!(decode_token_2022(&with_extensions.instructions[1]), TokenInstruction::InitializeNonTransferableMint);
+
+        let legacy_extensions = build_token_creation_instructions(&input(json!({
+            "mintAuthority": authority.to_string(),
+            "mint": mint.to_string(),
+            "decimals": 6,
+            "payer": payer.to_string(),
+            "extensions": [{ "type": "non_transferable" }],
+        })));
+        assert_eq!(legacy_extensions.err().as_deref(), Some("Mint extensions require token_program token-2022"));
+    }
+}
@@ -0,0 +1,601 @@
+use axum::{extract::{Json, State}, response::IntoResponse};
+use base64::{engine::general_purpose, Engine as _};
+use std::str::FromStr;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::{v0, Message, VersionedMessage};
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::program_utils::limited_deserialize;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::system_instruction::{self, SystemInstruction};
+use solana_sdk::system_program;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
+use crate::handlers::compute_budget::{estimate_fee, prepend_compute_budget};
+use crate::handlers::{memo, sol, stake, token};
+use crate::models::request::{AddressLookupTableInput, InstructionSpec, TransactionBuildInput, TransactionSigningInput};
+use crate::models::response::{LookupTableResolution, SignatureSlotOutput, TransactionBuildOutput, TransactionSigningOutput};
+use crate::services::keystore::resolve_keypair;
+use crate::state::AppState;
+use crate::utils::validation::*;
+use serde_json::json;
+
+const MAX_LOOKUP_TABLE_ADDRESSES: usize = 256;
+
+pub fn build_instructions_from_spec(spec: &InstructionSpec) -> Result<Vec<Instruction>, String> {
+    match spec {
+        InstructionSpec::TokenCreate(input) => {
+            token::build_token_creation_instructions(input).map(|creation| creation.instructions)
+        },
+        InstructionSpec::TokenMint(input) => token::build_token_minting_instructions(input),
+        InstructionSpec::TokenTransfer(input) => token::build_token_transfer_instructions(input),
+        InstructionSpec::AssociatedTokenAccount(input) => {
+            token::build_associated_token_account_instruction(input).map(|ix| vec![ix])
+        },
+        InstructionSpec::TokenBurn(input) => token::build_token_burn_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::TokenApprove(input) => token::build_token_approve_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::TokenRevoke(input) => token::build_token_revoke_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::TokenFreeze(input) => token::build_token_freeze_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::TokenThaw(input) => token::build_token_thaw_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::TokenClose(input) => token::build_token_close_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::TokenSetAuthority(input) => {
+            token::build_token_set_authority_instruction(input).map(|ix| vec![ix])
+        },
+        InstructionSpec::TokenMultisig(input) => token::build_multisig_creation_instructions(input),
+        InstructionSpec::WrapSol(input) => token::build_wrap_sol_instructions(input),
+        InstructionSpec::UnwrapSol(input) => token::build_unwrap_sol_instructions(input),
+        InstructionSpec::SolTransfer(input) => sol::build_sol_transfer_instructions(input),
+        InstructionSpec::CreateAccount(input) => sol::build_create_account_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::Allocate(input) => sol::build_allocate_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::Assign(input) => sol::build_assign_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::CreateAccountWithSeed(input) => {
+            sol::build_create_account_with_seed_instruction(input).map(|ix| vec![ix])
+        },
+        InstructionSpec::TransferWithSeed(input) => sol::build_transfer_with_seed_instructions(input),
+        InstructionSpec::AllocateWithSeed(input) => sol::build_allocate_with_seed_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::NonceCreate(input) => sol::build_nonce_creation_instructions(input),
+        InstructionSpec::NonceAdvance(input) => sol::build_nonce_advance_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::NonceWithdraw(input) => sol::build_nonce_withdraw_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::NonceAuthorize(input) => sol::build_nonce_authorize_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::StakeCreate(input) => stake::build_stake_creation_instructions(input),
+        InstructionSpec::StakeDelegate(input) => stake::build_stake_delegation_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::StakeDeactivate(input) => stake::build_stake_deactivation_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::StakeWithdraw(input) => stake::build_stake_withdraw_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::StakeSplit(input) => stake::build_stake_split_instructions(input),
+        InstructionSpec::StakeMerge(input) => stake::build_stake_merge_instructions(input),
+        InstructionSpec::StakeAuthorize(input) => stake::build_stake_authorize_instruction(input).map(|ix| vec![ix]),
+        InstructionSpec::Memo(input) => memo::build_memo_instruction(input).map(|ix| vec![ix]),
+    }
+}
+
+pub fn build_instructions_from_specs(specs: &[InstructionSpec]) -> Result<Vec<Instruction>, String> {
+    if specs.is_empty() {
+        return Err("At least one instruction is required".to_string());
+    }
+    let mut instructions = Vec::with_capacity(specs.len());
+    for (index, spec) in specs.iter().enumerate() {
+        let spec_instructions = build_instructions_from_spec(spec)
+            .map_err(|error_msg| format!("Instruction {}: {}", index, error_msg))?;
+        instructions.extend(spec_instructions);
+    }
+    Ok(instructions)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum TransactionVersion {
+    Legacy,
+    V0,
+}
+
+pub fn parse_transaction_version(version: Option<&str>) -> Result<TransactionVersion, String> {
+    match version.map(|v| v.trim()) {
+        None | Some("") | Some("legacy") => Ok(TransactionVersion::Legacy),
+        Some("0") | Some("v0") => Ok(TransactionVersion::V0),
+        Some(_) => Err("Invalid transaction version - expected \"legacy\" or \"v0\"".to_string()),
+    }
+}
+
+pub fn parse_lookup_tables(tables: &[AddressLookupTableInput]) -> Result<Vec<AddressLookupTableAccount>, String> {
+    tables
+        .iter()
+        .map(|table| {
+            let table_address = match table.address {
+                Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+                _ => return Err("Missing required fields".to_string()),
+            };
+            let parsed_table = validate_pubkey_format(table_address, "lookup table")?;
+            let table_addresses = match table.addresses {
+                Some(ref addresses) => addresses,
+                None => return Err("Missing required fields".to_string()),
+            };
+            if table_addresses.len() > MAX_LOOKUP_TABLE_ADDRESSES {
+                return Err(format!(
+                    "Lookup table {} has too many addresses - maximum is {}",
+                    parsed_table, MAX_LOOKUP_TABLE_ADDRESSES
+                ));
+            }
+            let parsed_addresses = table_addresses
+                .iter()
+                .map(|addr| validate_pubkey_format(addr, "lookup table entry"))
+                .collect::<Result<Vec<Pubkey>, String>>()?;
+            Ok(AddressLookupTableAccount {
+                key: parsed_table,
+                addresses: parsed_addresses,
+            })
+        })
+        .collect()
+}
+
+pub struct TransactionLifetime {
+    pub blockhash: Hash,
+    pub advance_nonce: Option<Instruction>,
+}
+
+pub fn resolve_transaction_lifetime(
+    recent_blockhash: Option<&str>,
+    nonce_account: Option<&str>,
+    nonce_value: Option<&str>,
+    nonce_authority: Option<&str>,
+    fee_payer: &Pubkey,
+) -> Result<TransactionLifetime, String> {
+    let recent_blockhash = recent_blockhash.map(str::trim).filter(|hash| !hash.is_empty());
+    let nonce_account = nonce_account.map(str::trim).filter(|addr| !addr.is_empty());
+    match (recent_blockhash, nonce_account) {
+        (Some(_), Some(_)) => Err("Provide either recent_blockhash or nonce_account, not both".to_string()),
+        (Some(blockhash), None) => Ok(TransactionLifetime {
+            blockhash: validate_blockhash_format(blockhash)?,
+            advance_nonce: None,
+        }),
+        (None, Some(nonce_address)) => {
+            let nonce_hash = match nonce_value.map(str::trim) {
+                Some(value) if !value.is_empty() => Hash::from_str(value)
+                    .map_err(|_| "Invalid nonce value".to_string())?,
+                _ => return Err("Missing required fields".to_string()),
+            };
+            let parsed_nonce = validate_pubkey_format(nonce_address, "nonce account")?;
+            let parsed_authority = match nonce_authority.map(str::trim) {
+                Some(addr) if !addr.is_empty() => validate_pubkey_format(addr, "nonce authority")?,
+                _ => *fee_payer,
+            };
+            Ok(TransactionLifetime {
+                blockhash: nonce_hash,
+                advance_nonce: Some(system_instruction::advance_nonce_account(&parsed_nonce, &parsed_authority)),
+            })
+        },
+        (None, None) => Err("Missing required fields".to_string()),
+    }
+}
+
+pub fn compile_transaction(
+    instructions: &[Instruction],
+    fee_payer: &Pubkey,
+    recent_blockhash: &Hash,
+    version: TransactionVersion,
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<TransactionBuildOutput, String> {
+    let (transaction_bytes, message_bytes, signers, static_keys, lookup_table_keys): (_, _, Vec<String>, _, _) = match version {
+        TransactionVersion::Legacy => {
+            if !lookup_tables.is_empty() {
+                return Err("Address lookup tables require a v0 transaction".to_string());
+            }
+            let message = Message::new_with_blockhash(instructions, Some(fee_payer), recent_blockhash);
+            let signers = message.account_keys
+                .iter()
+                .take(message.header.num_required_signatures as usize)
+                .map(|key| key.to_string())
+                .collect();
+            let static_keys = message.account_keys.iter().map(|key| key.to_string()).collect();
+            let message_bytes = message.serialize();
+            let transaction_bytes = bincode::serialize(&Transaction::new_unsigned(message))
+                .map_err(|error| format!("Failed to serialize transaction: {}", error))?;
+            (transaction_bytes, message_bytes, signers, static_keys, Vec::new())
+        },
+        TransactionVersion::V0 => {
+            let message = v0::Message::try_compile(fee_payer, instructions, lookup_tables, *recent_blockhash)
+                .map_err(|error| format!("Failed to compile v0 message: {}", error))?;
+            let signer_count = message.header.num_required_signatures as usize;
+            let signers = message.account_keys
+                .iter()
+                .take(signer_count)
+                .map(|key| key.to_string())
+                .collect();
+            let static_keys = message.account_keys.iter().map(|key| key.to_string()).collect();
+            let lookup_table_keys = message.address_table_lookups
+                .iter()
+                .map(|lookup| {
+                    let table = lookup_tables
+                        .iter()
+                        .find(|table| table.key == lookup.account_key)
+                        .ok_or_else(|| format!("Unknown lookup table {}", lookup.account_key))?;
+                    let resolve = |indexes: &[u8]| -> Vec<String> {
+                        indexes.iter().map(|index| table.addresses[*index as usize].to_string()).collect()
+                    };
+                    Ok(LookupTableResolution {
+                        table: lookup.account_key.to_string(),
+                        writable: resolve(&lookup.writable_indexes),
+                        readonly: resolve(&lookup.readonly_indexes),
+                    })
+                })
+                .collect::<Result<Vec<LookupTableResolution>, String>>()?;
+            let versioned_message = VersionedMessage::V0(message);
+            let message_bytes = versioned_message.serialize();
+            let unsigned_transaction = VersionedTransaction {
+                signatures: vec![Signature::default(); signer_count],
+                message: versioned_message,
+            };
+            let transaction_bytes = bincode::serialize(&unsigned_transaction)
+                .map_err(|error| format!("Failed to serialize transaction: {}", error))?;
+            (transaction_bytes, message_bytes, signers, static_keys, lookup_table_keys)
+        },
+    };
+    if transaction_bytes.len() > PACKET_DATA_SIZE {
+        return Err(format!(
+            "Transaction too large - {} bytes exceeds the {} byte limit",
+            transaction_bytes.len(),
+            PACKET_DATA_SIZE
+        ));
+    }
+    let fee_estimate = estimate_fee(instructions, signers.len());
+    Ok(TransactionBuildOutput {
+        transaction: general_purpose::STANDARD.encode(&transaction_bytes),
+        message: general_purpose::STANDARD.encode(&message_bytes),
+        version: match version {
+            TransactionVersion::Legacy => "legacy".to_string(),
+            TransactionVersion::V0 => "v0".to_string(),
+        },
+        signers,
+        fee_payer: fee_payer.to_string(),
+        recent_blockhash: recent_blockhash.to_string(),
+        nonce_account: nonce_account_of(instructions),
+        static_keys,
+        lookup_table_keys,
+        fee_estimate,
+    })
+}
+
+fn nonce_account_of(instructions: &[Instruction]) -> Option<String> {
+    let first_instruction = instructions.first()?;
+    let is_advance_nonce = first_instruction.program_id == system_program::ID
+        && matches!(
+            limited_deserialize(&first_instruction.data),
+            Ok(SystemInstruction::AdvanceNonceAccount)
+        );
+    is_advance_nonce.then(|| first_instruction.accounts[0].pubkey.to_string())
+}
+
+pub async fn handle_transaction_build(Json(input): Json<TransactionBuildInput>) -> impl IntoResponse {
+    let instruction_specs = match input.instructions {
+        Some(ref specs) => specs,
+        None => return build_error_response("Missing required fields"),
+    };
+    let fee_payer_address = match input.fee_payer {
+        Some(ref addr) if !addr.trim().is_empty() => addr.trim(),
+        _ => return build_error_response("Missing required fields"),
+    };
+    let transaction_version = match parse_transaction_version(input.version.as_deref()) {
+        Ok(version) => version,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let parsed_fee_payer = match validate_pubkey_format(fee_payer_address, "fee payer") {
+        Ok(addr) => addr,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let transaction_lifetime = match resolve_transaction_lifetime(
+        input.recent_blockhash.as_deref(),
+        input.nonce_account.as_deref(),
+        input.nonce_value.as_deref(),
+        input.nonce_authority.as_deref(),
+        &parsed_fee_payer,
+    ) {
+        Ok(lifetime) => lifetime,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let lookup_tables = match parse_lookup_tables(input.address_lookup_tables.as_deref().unwrap_or_default()) {
+        Ok(tables) => tables,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let mut instructions = match build_instructions_from_specs(instruction_specs) {
+        Ok(instructions) => instructions,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    instructions = match prepend_compute_budget(instructions, &input.compute_budget) {
+        Ok(instructions) => instructions,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    if let Some(advance_instruction) = transaction_lifetime.advance_nonce {
+        instructions.insert(0, advance_instruction);
+    }
+    match compile_transaction(
+        &instructions,
+        &parsed_fee_payer,
+        &transaction_lifetime.blockhash,
+        transaction_version,
+        &lookup_tables,
+    ) {
+        Ok(build_result) => build_success_response(build_result),
+        Err(error_msg) => build_error_response(&error_msg),
+    }
+}
+
+pub fn decode_transaction(encoded_transaction: &str) -> Result<VersionedTransaction, String> {
+    let transaction_bytes = general_purpose::STANDARD
+        .decode(encoded_transaction.trim())
+        .map_err(|_| "Invalid transaction encoding - expected base64".to_string())?;
+    if transaction_bytes.len() > PACKET_DATA_SIZE {
+        return Err("Invalid transaction - exceeds maximum packet size".to_string());
+    }
+    let transaction: VersionedTransaction = bincode::deserialize(&transaction_bytes)
+        .map_err(|_| "Invalid transaction format".to_string())?;
+    transaction
+        .sanitize()
+        .map_err(|error| format!("Invalid transaction: {}", error))?;
+    Ok(transaction)
+}
+
+pub fn sign_transaction(transaction: &mut VersionedTransaction, signing_keypairs: &[Keypair]) -> Result<(), String> {
+    let required_signers = transaction.message.header().num_required_signatures as usize;
+    let signer_keys = transaction.message.static_account_keys()[..required_signers].to_vec();
+    let message_bytes = transaction.message.serialize();
+    for keypair in signing_keypairs {
+        let signer_index = signer_keys
+            .iter()
+            .position(|key| *key == keypair.pubkey())
+            .ok_or_else(|| format!("{} is not a required signer of this transaction", keypair.pubkey()))?;
+        transaction.signatures[signer_index] = keypair.sign_message(&message_bytes);
+    }
+    Ok(())
+}
+
+pub async fn handle_transaction_signing(
+    State(state): State<AppState>,
+    Json(input): Json<TransactionSigningInput>,
+) -> impl IntoResponse {
+    let encoded_transaction = match input.transaction {
+        Some(ref tx) if !tx.trim().is_empty() => tx.trim(),
+        _ => return build_error_response("Missing required fields"),
+    };
+    let signer_secrets = input.secrets.as_deref().unwrap_or_default();
+    let signer_key_ids = input.key_ids.as_deref().unwrap_or_default();
+    if signer_secrets.is_empty() && signer_key_ids.is_empty() {
+        return build_error_response("Missing required fields");
+    }
+    let mut transaction = match decode_transaction(encoded_transaction) {
+        Ok(tx) => tx,
+        Err(error_msg) => return build_error_response(&error_msg),
+    };
+    let mut signing_keypairs = Vec::with_capacity(signer_secrets.len() + signer_key_ids.len());
+    for secret_key in signer_secrets {
+        match resolve_keypair(state.keystore.as_ref(), Some(secret_key), None).await {
+            Ok(keypair) => signing_keypairs.push(keypair),
+            Err(error_msg) => return build_error_response(&error_msg),
+        }
+    }
+    for key_id in signer_key_ids {
+        match resolve_keypair(state.keystore.as_ref(), None, Some(key_id)).await {
+            Ok(keypair) => signing_keypairs.push(keypair),
+            Err(error_msg) => return build_error_response(&error_msg),
+        }
+    }
+    if let Err(error_msg) = sign_transaction(&mut transaction, &signing_keypairs) {
+        return build_error_response(&error_msg);
+    }
+    let signature_slots: Vec<SignatureSlotOutput> = transaction.message
+        .static_account_keys()
+        .iter()
+        .zip(transaction.signatures.iter())
+        .map(|(pubkey, signature)| SignatureSlotOutput {
+            pubkey: pubkey.to_string(),
+            signature: (*signature != Signature::default()).then(|| signature.to_string()),
+        })
+        .collect();
+    let missing_signers: Vec<String> = signature_slots
+        .iter()
+        .filter(|slot| slot.signature.is_none())
+        .map(|slot| slot.pubkey.clone())
+        .collect();
+    let transaction_bytes = match bincode::serialize(&transaction) {
+        Ok(bytes) => bytes,
+        Err(error) => return build_error_response(&format!("Failed to serialize transaction: {}", error)),
+    };
+    build_success_response(TransactionSigningOutput {
+        transaction: general_purpose::STANDARD.encode(&transaction_bytes),
+        fully_signed: missing_signers.is_empty(),
+        signatures: signature_slots,
+        missing_signers,
+    })
+}
+
+fn build_success_response<T: serde::Serialize>(data: T) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::OK, axum::Json(json!({ "success": true, "data": data })))
+}
+
+fn build_error_response(error_message: &str) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({ "success": false, "error": error_message })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::message::VersionedMessage;
+
+    fn sol_transfer_spec(from: &Pubkey, to: &Pubkey, lamports: u64) -> InstructionSpec {
+        serde_json::from_value(json!({
+            "type": "sol_transfer",
+            "from": from.to_string(),
+            "to": to.to_string(),
+            "lamports": lamports,
+        }))
+        .unwrap()
+    }
+
+    fn compile_and_decode(
+        instructions: &[Instruction],
+        fee_payer: &Pubkey,
+        version: TransactionVersion,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> (TransactionBuildOutput, VersionedTransaction) {
+        let build_output = compile_transaction(instructions, fee_payer, &Hash::new_unique(), version, lookup_tables).unwrap();
+        let transaction = decode_transaction(&build_output.transaction).unwrap();
+        (build_output, transaction)
+    }
+
+    #[test]
+    fn builds_instructions_in_spec_order() {
+        let (payer, first, second) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let instructions = build_instructions_from_specs(&[
+            sol_transfer_spec(&payer, &first, 10),
+            sol_transfer_spec(&payer, &second, 20),
+        ])
+        .unwrap();
+        assert_eq!(instructions, [
+            system_instruction::transfer(&payer, &first, 10),
+            system_instruction::transfer(&payer, &second, 20),
+        ]);
+    }
+
+    #[test]
+    fn spec_errors_carry_instruction_index() {
+        let payer = Pubkey::new_unique();
+        assert_eq!(
+            build_instructions_from_specs(&[]).unwrap_err(),
+            "At least one instruction is required"
+        );
+        let error = build_instructions_from_specs(&[
+            sol_transfer_spec(&payer, &Pubkey::new_unique(), 10),
+            sol_transfer_spec(&payer, &payer, 10),
+        ])
+        .unwrap_err();
+        assert!(error.starts_with("Instruction 1: "), "{}", error);
+    }
+
+    #[test]
+    fn legacy_transaction_round_trips() {
+        let (payer, recipient) = (Keypair::new(), Pubkey::new_unique());
+        let instructions = build_instructions_from_specs(&[sol_transfer_spec(&payer.pubkey(), &recipient, 5_000)]).unwrap();
+        let (build_output, transaction) =
+            compile_and_decode(&instructions, &payer.pubkey(), TransactionVersion::Legacy, &[]);
+        assert_eq!(build_output.version, "legacy");
+        assert_eq!(build_output.signers, [payer.pubkey().to_string()]);
+        assert!(matches!(transaction.message, VersionedMessage::Legacy(_)));
+        assert_eq!(transaction.signatures, [Signature::default()]);
+        assert_eq!(transaction.message.static_account_keys()[..2], [payer.pubkey(), recipient]);
+        assert_eq!(transaction.message.recent_blockhash().to_string(), build_output.recent_blockhash);
+        assert_eq!(general_purpose::STANDARD.encode(transaction.message.serialize()), build_output.message);
+    }
+
+    #[test]
+    fn v0_transaction_round_trips() {
+        let (payer, recipient) = (Keypair::new(), Pubkey::new_unique());
+        let instructions = build_instructions_from_specs(&[sol_transfer_spec(&payer.pubkey(), &recipient, 5_000)]).unwrap();
+        let (build_output, transaction) =
+            compile_and_decode(&instructions, &payer.pubkey(), TransactionVersion::V0, &[]);
+        assert_eq!(build_output.version, "v0");
+        assert!(build_output.lookup_table_keys.is_empty());
+        match transaction.message {
+            VersionedMessage::V0(ref message) => assert!(message.address_table_lookups.is_empty()),
+            VersionedMessage::Legacy(_) => panic!("expected a v0 message"),
+        }
+        assert_eq!(transaction.signatures, [Signature::default()]);
+    }
+
+    #[test]
+    fn lookup_table_keys_move_out_of_static_keys() {
+        let (payer, recipient) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let table_address = Pubkey::new_unique();
+        let lookup_tables = parse_lookup_tables(&[AddressLookupTableInput {
+            address: Some(table_address.to_string()),
+            addresses: Some(vec![Pubkey::new_unique().to_string(), recipient.to_string()]),
+        }])
+        .unwrap();
+        let instructions = [system_instruction::transfer(&payer, &recipient, 1)];
+        let (build_output, transaction) =
+            compile_and_decode(&instructions, &payer, TransactionVersion::V0, &lookup_tables);
+        let message = match transaction.message {
+            VersionedMessage::V0(message) => message,
+            VersionedMessage::Legacy(_) => panic!("expected a v0 message"),
+        };
+        assert!(!message.account_keys.contains(&recipient));
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert_eq!(message.address_table_lookups[0].account_key, table_address);
+        assert_eq!(message.address_table_lookups[0].writable_indexes, [1]);
+        assert!(message.address_table_lookups[0].readonly_indexes.is_empty());
+        assert_eq!(build_output.lookup_table_keys.len(), 1);
+        assert_eq!(build_output.lookup_table_keys[0].writable, [recipient.to_string()]);
+    }
+
+    #[test]
+    fn legacy_transactions_reject_lookup_tables() {
+        let payer = Pubkey::new_unique();
+        let lookup_tables = [AddressLookupTableAccount { key: Pubkey::new_unique(), addresses: vec![] }];
+        let compiled = compile_transaction(
+            &[system_instruction::transfer(&payer, &Pubkey::new_unique(), 1)],
+            &payer,
+            &Hash::new_unique(),
+            TransactionVersion::Legacy,
+            &lookup_tables,
+        );
+        assert_eq!(compiled.err().as_deref(), Some("Address lookup tables require a v0 transaction"));
+    }
+
+    #[test]
+    fn lookup_table_input_is_validated() {
+        let missing_addresses = parse_lookup_tables(&[AddressLookupTableInput {
+            address: Some(Pubkey::new_unique().to_string()),
+            addresses: None,
+        }]);
+        assert_eq!(missing_addresses.unwrap_err(), "Missing required fields");
+        let oversized_table = parse_lookup_tables(&[AddressLookupTableInput {
+            address: Some(Pubkey::new_unique().to_string()),
+            addresses: Some(vec![Pubkey::new_unique().to_string(); MAX_LOOKUP_TABLE_ADDRESSES + 1]),
+        }]);
+        assert!(oversized_table.unwrap_err().contains("too many addresses"));
+        let invalid_entry = parse_lookup_tables(&[AddressLookupTableInput {
+            address: Some(Pubkey::new_unique().to_string()),
+            addresses: Some(vec!["not-a-pubkey".to_string()]),
+        }]);
+        assert!(invalid_entry.is_err());
+    }
+
+    #[test]
+    fn partial_signing_leaves_other_slots_empty() {
+        let (payer, co_signer) = (Keypair::new(), Keypair::new());
+        let instructions = [
+            system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1),
+            system_instruction::transfer(&co_signer.pubkey(), &Pubkey::new_unique(), 1),
+        ];
+        for version in [TransactionVersion::Legacy, TransactionVersion::V0] {
+            let (_, mut transaction) = compile_and_decode(&instructions, &payer.pubkey(), version, &[]);
+            assert_eq!(transaction.signatures.len(), 2);
+            sign_transaction(&mut transaction, &[co_signer.insecure_clone()]).unwrap();
+            let signer_keys = transaction.message.static_account_keys();
+            assert_eq!(signer_keys[..2], [payer.pubkey(), co_signer.pubkey()]);
+            assert_eq!(transaction.signatures[0], Signature::default());
+            assert!(transaction.signatures[1].verify(co_signer.pubkey().as_ref(), &transaction.message.serialize()));
+            let reencoded = general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap());
+            assert_eq!(decode_transaction(&reencoded).unwrap().signatures, transaction.signatures);
+        }
+    }
+
+    #[test]
+    fn signing_rejects_non_signers() {
+        let payer = Keypair::new();
+        let instructions = [system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1)];
+        let (_, mut transaction) = compile_and_decode(&instructions, &payer.pubkey(), TransactionVersion::Legacy, &[]);
+        let stranger = Keypair::new();
+        let error = sign_transaction(&mut transaction, &[stranger.insecure_clone()]).unwrap_err();
+        assert_eq!(error, format!("{} is not a required signer of this transaction", stranger.pubkey()));
+    }
+
+    #[test]
+    fn decode_rejects_malformed_input() {
+        assert_eq!(
+            decode_transaction("not base64!").unwrap_err(),
+            "Invalid transaction encoding - expected base64"
+        );
+        assert_eq!(
+            decode_transaction(&general_purpose::STANDARD.encode([1, 2, 3])).unwrap_err(),
+            "Invalid transaction format"
+        );
+    }
+}
@@ -1,24 +1,115 @@
 mod handlers;
 mod models;
+mod services;
+mod state;
 mod utils;
 
-use axum::{routing::post, Router};
+use std::path::PathBuf;
+use std::sync::Arc;
+use axum::{routing::{get, post}, Router};
+use services::keystore::Keystore;
+use services::pay_templates::PayTemplates;
+use services::siws::SiwsStore;
+use services::rpc::{HttpRpcBackend, MockRpcBackend};
+use state::AppState;
 
 #[tokio::main]
 async fn main() {
+    let mut app_state = AppState::default();
+    if let Ok(keystore_dir) = std::env::var("KEYSTORE_DIR") {
+        let passphrase = std::env::var("KEYSTORE_PASSPHRASE")
+            .expect("KEYSTORE_PASSPHRASE must be set when KEYSTORE_DIR is set");
+        let keystore = Keystore::open(&PathBuf::from(&keystore_dir), &passphrase)
+            .expect("Failed to open keystore");
+        println!("Keystore loaded from {}", keystore_dir);
+        app_state.keystore = Some(Arc::new(keystore));
+    }
+    if let Ok(rpc_url) = std::env::var("RPC_URL") {
+        if rpc_url == "mock" {
+            app_state.rpc = Some(Arc::new(MockRpcBackend::new()));
+        } else {
+            app_state.rpc = Some(Arc::new(HttpRpcBackend::new(&rpc_url)));
+        }
+        println!("RPC backend configured: {}", rpc_url);
+    }
+    if let Ok(templates_path) = std::env::var("SOLANA_PAY_TEMPLATES") {
+        let pay_templates = PayTemplates::load(&PathBuf::from(&templates_path))
+            .expect("Failed to load Solana Pay templates");
+        println!("Solana Pay templates loaded from {}", templates_path);
+        app_state.pay_templates = Some(Arc::new(pay_templates));
+    }
+    if let Ok(session_secret) = std::env::var("SIWS_SESSION_SECRET") {
+        app_state.siws = Arc::new(SiwsStore::new(session_secret.as_bytes()));
+    }
+
     let app = Router::new()
         .route("/keypair", post(handlers::keypair::handle_keypair_generation))
+        .route("/keypair/mnemonic", post(handlers::keypair::handle_mnemonic_generation))
+        .route("/keypair/recover", post(handlers::keypair::handle_keypair_recovery))
         .route("/token/create", post(handlers::token::handle_token_creation))
         .route("/token/mint", post(handlers::token::handle_token_minting))
+        .route("/token/ata", post(handlers::token::handle_associated_token_account))
+        .route("/token/burn", post(handlers::token::handle_token_burn))
+        .route("/token/approve", post(handlers::token::handle_token_approve))
+        .route("/token/revoke", post(handlers::token::handle_token_revoke))
+        .route("/token/freeze", post(handlers::token::handle_token_freeze))
+        .route("/token/thaw", post(handlers::token::handle_token_thaw))
+        .route("/token/close", post(handlers::token::handle_token_close))
+        .route("/token/authority", post(handlers::token::handle_token_set_authority))
+        .route("/token/multisig", post(handlers::token::handle_multisig_creation))
+        .route("/token/wrap", post(handlers::token::handle_sol_wrap))
+        .route("/token/unwrap", post(handlers::token::handle_sol_unwrap))
         .route("/send/token", post(handlers::token::handle_token_transfer))
         .route("/message/sign", post(handlers::message::handle_message_signing))
         .route("/message/verify", post(handlers::message::handle_message_verification))
+        .route("/siws/challenge", post(handlers::siws::handle_siws_challenge))
+        .route("/siws/verify", post(handlers::siws::handle_siws_verification))
         .route("/send/sol", post(handlers::sol::handle_sol_transfer))
-        ;
+        .route("/system/create-account", post(handlers::sol::handle_create_account))
+        .route("/system/allocate", post(handlers::sol::handle_allocate))
+        .route("/system/assign", post(handlers::sol::handle_assign))
+        .route("/system/create-account-with-seed", post(handlers::sol::handle_create_account_with_seed))
+        .route("/system/transfer-with-seed", post(handlers::sol::handle_transfer_with_seed))
+        .route("/system/allocate-with-seed", post(handlers::sol::handle_allocate_with_seed))
+        .route("/system/rent", post(handlers::sol::handle_rent_exemption))
+        .route("/nonce/create", post(handlers::sol::handle_nonce_creation))
+        .route("/nonce/advance", post(handlers::sol::handle_nonce_advance))
+        .route("/nonce/withdraw", post(handlers::sol::handle_nonce_withdraw))
+        .route("/nonce/authorize", post(handlers::sol::handle_nonce_authorize))
+        .route("/stake/create", post(handlers::stake::handle_stake_creation))
+        .route("/stake/delegate", post(handlers::stake::handle_stake_delegation))
+        .route("/stake/deactivate", post(handlers::stake::handle_stake_deactivation))
+        .route("/stake/withdraw", post(handlers::stake::handle_stake_withdraw))
+        .route("/stake/split", post(handlers::stake::handle_stake_split))
+        .route("/stake/merge", post(handlers::stake::handle_stake_merge))
+        .route("/stake/authorize", post(handlers::stake::handle_stake_authorize))
+        .route("/transaction/build", post(handlers::transaction::handle_transaction_build))
+        .route("/transaction/sign", post(handlers::transaction::handle_transaction_signing))
+        .route("/inspect", post(handlers::inspect::handle_inspection))
+        .route("/inspect/account", post(handlers::inspect::handle_account_decoding))
+        .route("/address/pda/find", post(handlers::address::handle_find_program_address))
+        .route("/address/pda/create", post(handlers::address::handle_create_program_address))
+        .route("/address/seed", post(handlers::address::handle_seed_address))
+        .route("/address/ata", post(handlers::address::handle_associated_token_address))
+        .route("/memo", post(handlers::memo::handle_memo))
+        .route("/pay/url", post(handlers::solana_pay::handle_transfer_request_url))
+        .route("/pay/parse", post(handlers::solana_pay::handle_transfer_request_parsing))
+        .route(
+            "/pay/request/:template",
+            get(handlers::solana_pay::handle_transaction_request_metadata)
+                .post(handlers::solana_pay::handle_transaction_request),
+        )
+        .route("/rpc/blockhash", get(handlers::rpc::handle_latest_blockhash))
+        .route("/rpc/send", post(handlers::rpc::handle_transaction_submission))
+        .route("/rpc/status", post(handlers::rpc::handle_signature_status))
+        .route("/keystore/keys", get(handlers::keystore::handle_keystore_list))
+        .route("/keystore/delete", post(handlers::keystore::handle_keystore_delete))
+        .route("/keystore/export", post(handlers::keystore::handle_keystore_export))
+        .with_state(app_state);
 
     let tcp_listener = tokio::net::TcpListener::bind("0.0.0.0:3001")
         .await
         .expect("Failed to bind to port 3001");
     println!("Solana HTTP server running on http://0.0.0.0:3001");
     axum::serve(tcp_listener, app).await.unwrap();
-}
\ No newline at end of file
+}
@@ -1,54 +1,26 @@
 use serde::Deserialize;
 
-#[derive(Deserialize)]
-pub struct GenerateKeypairRequest {
-    pub mint_authority: String,
-    pub mint: String,
-    pub decimals: u8,
-}
-
-#[derive(Deserialize)]
-pub struct CreateTokenRequest {
-    #[serde(rename = "mintAuthority")]
-    pub mint_authority: String,
-    pub mint: String,
-    pub decimals: u8,
-}
-
-#[derive(Deserialize)]
-pub struct MintTokenRequest {
-    pub mint: String,
-    pub destination: String,
-    pub authority: String,
-    pub amount: u64,
+#[derive(Deserialize, Default)]
+pub struct KeypairGenerationInput {
+    pub persist: Option<bool>,
 }
 
-#[derive(Deserialize)]
-pub struct SignMessageRequest {
-    pub message: String,
-    pub secret: String,
-}
-
-#[derive(Deserialize)]
-pub struct VerifyMessageRequest {
-    pub message: String,
-    pub signature: String,
-    pub pubkey: String,
-}
-
-#[derive(Deserialize)]
-pub struct SendSolRequest {
-    pub from: String,
-    pub to: String,
-    pub lamports: u64,
+#[derive(Deserialize, Default)]
+pub struct MnemonicGenerationInput {
+    pub word_count: Option<usize>,
+    pub passphrase: Option<String>,
+    pub derivation_path: Option<String>,
+    pub start_index: Option<u32>,
+    pub count: Option<u32>,
 }
 
 #[derive(Deserialize)]
-pub struct SendTokenRequest {
-    pub destination: String,
-    pub mint: String,
-    pub owner: String,
-    pub amount: u64,
+pub struct KeypairRecoveryInput {
+    pub mnemonic: Option<String>,
+    pub passphrase: Option<String>,
+    pub derivation_path: Option<String>,
+    pub start_index: Option<u32>,
+    pub count: Option<u32>,
 }
 
 #[derive(Deserialize)]
@@ -57,21 +29,51 @@ pub struct TokenCreationInput {
     pub mint_authority: Option<String>,
     pub mint: Option<String>,
     pub decimals: Option<u8>,
+    pub token_program: Option<String>,
+    pub payer: Option<String>,
+    pub extensions: Option<Vec<MintExtensionInput>>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MintExtensionInput {
+    TransferFee {
+        transfer_fee_config_authority: Option<String>,
+        withdraw_withheld_authority: Option<String>,
+        transfer_fee_basis_points: Option<u16>,
+        #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+        maximum_fee: Option<u64>,
+    },
+    PermanentDelegate {
+        delegate: Option<String>,
+    },
+    NonTransferable,
+    InterestBearing {
+        rate_authority: Option<String>,
+        rate: Option<i16>,
+    },
 }
 
 #[derive(Deserialize)]
 pub struct TokenMintingInput {
     pub mint: Option<String>,
     pub destination: Option<String>,
+    pub destination_owner: Option<String>,
+    pub payer: Option<String>,
     pub authority: Option<String>,
-    #[serde(deserialize_with = "crate::utils::parse_amount_field")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
     pub amount: Option<u64>,
+    pub decimals: Option<u8>,
+    pub token_program: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct MessageSigningInput {
     pub message: Option<String>,
     pub secret: Option<String>,
+    pub key_id: Option<String>,
+    pub offchain: Option<bool>,
 }
 
 #[derive(Deserialize)]
@@ -79,21 +81,493 @@ pub struct MessageVerificationInput {
     pub message: Option<String>,
     pub signature: Option<String>,
     pub pubkey: Option<String>,
+    pub offchain: Option<bool>,
 }
 
 #[derive(Deserialize)]
 pub struct SolTransferInput {
     pub from: Option<String>,
     pub to: Option<String>,
-    #[serde(deserialize_with = "crate::utils::parse_amount_field")]
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub lamports: Option<u64>,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateAccountInput {
+    pub from: Option<String>,
+    pub new_account: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub lamports: Option<u64>,
+    pub space: Option<u64>,
+    pub owner: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AllocateInput {
+    pub account: Option<String>,
+    pub space: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct AssignInput {
+    pub account: Option<String>,
+    pub owner: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateAccountWithSeedInput {
+    pub from: Option<String>,
+    pub base: Option<String>,
+    pub seed: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub lamports: Option<u64>,
+    pub space: Option<u64>,
+    pub owner: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TransferWithSeedInput {
+    pub base: Option<String>,
+    pub seed: Option<String>,
+    pub from_owner: Option<String>,
+    pub to: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
     pub lamports: Option<u64>,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AllocateWithSeedInput {
+    pub base: Option<String>,
+    pub seed: Option<String>,
+    pub space: Option<u64>,
+    pub owner: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct NonceCreationInput {
+    pub from: Option<String>,
+    pub nonce_account: Option<String>,
+    pub authority: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub lamports: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct NonceAdvanceInput {
+    pub nonce_account: Option<String>,
+    pub authority: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct NonceWithdrawInput {
+    pub nonce_account: Option<String>,
+    pub authority: Option<String>,
+    pub to: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub lamports: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct NonceAuthorizeInput {
+    pub nonce_account: Option<String>,
+    pub authority: Option<String>,
+    pub new_authority: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StakeLockupInput {
+    pub unix_timestamp: Option<i64>,
+    pub epoch: Option<u64>,
+    pub custodian: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StakeCreationInput {
+    pub from: Option<String>,
+    pub stake_account: Option<String>,
+    pub staker: Option<String>,
+    pub withdrawer: Option<String>,
+    pub lockup: Option<StakeLockupInput>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub lamports: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct StakeDelegationInput {
+    pub stake_account: Option<String>,
+    pub vote_account: Option<String>,
+    pub staker: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StakeDeactivationInput {
+    pub stake_account: Option<String>,
+    pub staker: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StakeWithdrawInput {
+    pub stake_account: Option<String>,
+    pub withdrawer: Option<String>,
+    pub to: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub lamports: Option<u64>,
+    pub custodian: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StakeSplitInput {
+    pub stake_account: Option<String>,
+    pub staker: Option<String>,
+    pub split_stake_account: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub lamports: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct StakeMergeInput {
+    pub destination_stake_account: Option<String>,
+    pub source_stake_account: Option<String>,
+    pub staker: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StakeAuthorizeInput {
+    pub stake_account: Option<String>,
+    pub authority: Option<String>,
+    pub new_authority: Option<String>,
+    pub authority_type: Option<String>,
+    pub custodian: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RentExemptionInput {
+    pub space: Option<u64>,
 }
 
 #[derive(Deserialize)]
 pub struct TokenTransferInput {
     pub destination: Option<String>,
+    pub destination_owner: Option<String>,
+    pub payer: Option<String>,
     pub mint: Option<String>,
     pub owner: Option<String>,
-    #[serde(deserialize_with = "crate::utils::parse_amount_field")]
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub amount: Option<u64>,
+    pub ui_amount: Option<String>,
+    pub decimals: Option<u8>,
+    pub mint_account_data: Option<String>,
+    pub checked: Option<bool>,
+    pub token_program: Option<String>,
+    pub memo: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AssociatedTokenAccountInput {
+    pub owner: Option<String>,
+    pub mint: Option<String>,
+    pub payer: Option<String>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenMultisigInput {
+    pub multisig: Option<String>,
+    pub signers: Option<Vec<String>>,
+    pub threshold: Option<u8>,
+    pub payer: Option<String>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenBurnInput {
+    pub account: Option<String>,
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub multisig_signers: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub amount: Option<u64>,
+    pub decimals: Option<u8>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenApproveInput {
+    pub account: Option<String>,
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub delegate: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
     pub amount: Option<u64>,
-}
\ No newline at end of file
+    pub decimals: Option<u8>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenRevokeInput {
+    pub account: Option<String>,
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenFreezeInput {
+    pub account: Option<String>,
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub freeze_authority: Option<String>,
+    pub multisig_signers: Option<Vec<String>>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenCloseInput {
+    pub account: Option<String>,
+    pub mint: Option<String>,
+    pub owner: Option<String>,
+    pub destination: Option<String>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenSetAuthorityInput {
+    pub account: Option<String>,
+    pub authority_type: Option<String>,
+    pub current_authority: Option<String>,
+    pub multisig_signers: Option<Vec<String>>,
+    pub new_authority: Option<String>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct WrapSolInput {
+    pub owner: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub lamports: Option<u64>,
+    pub payer: Option<String>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UnwrapSolInput {
+    pub owner: Option<String>,
+    pub account: Option<String>,
+    pub destination: Option<String>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct ComputeBudgetInput {
+    pub compute_unit_limit: Option<u32>,
+    #[serde(default, deserialize_with = "crate::utils::parse_amount_field")]
+    pub priority_fee_micro_lamports: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct ComputeBudgeted<T> {
+    #[serde(flatten)]
+    pub request: T,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetInput,
+}
+
+#[derive(Deserialize)]
+pub struct MemoInput {
+    pub memo: Option<String>,
+    pub signers: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InstructionSpec {
+    TokenCreate(TokenCreationInput),
+    TokenMint(TokenMintingInput),
+    TokenTransfer(TokenTransferInput),
+    AssociatedTokenAccount(AssociatedTokenAccountInput),
+    TokenBurn(TokenBurnInput),
+    TokenApprove(TokenApproveInput),
+    TokenRevoke(TokenRevokeInput),
+    TokenFreeze(TokenFreezeInput),
+    TokenThaw(TokenFreezeInput),
+    TokenClose(TokenCloseInput),
+    TokenSetAuthority(TokenSetAuthorityInput),
+    TokenMultisig(TokenMultisigInput),
+    WrapSol(WrapSolInput),
+    UnwrapSol(UnwrapSolInput),
+    SolTransfer(SolTransferInput),
+    CreateAccount(CreateAccountInput),
+    Allocate(AllocateInput),
+    Assign(AssignInput),
+    CreateAccountWithSeed(CreateAccountWithSeedInput),
+    TransferWithSeed(TransferWithSeedInput),
+    AllocateWithSeed(AllocateWithSeedInput),
+    NonceCreate(NonceCreationInput),
+    NonceAdvance(NonceAdvanceInput),
+    NonceWithdraw(NonceWithdrawInput),
+    NonceAuthorize(NonceAuthorizeInput),
+    StakeCreate(StakeCreationInput),
+    StakeDelegate(StakeDelegationInput),
+    StakeDeactivate(StakeDeactivationInput),
+    StakeWithdraw(StakeWithdrawInput),
+    StakeSplit(StakeSplitInput),
+    StakeMerge(StakeMergeInput),
+    StakeAuthorize(StakeAuthorizeInput),
+    Memo(MemoInput),
+}
+
+#[derive(Deserialize)]
+pub struct AddressLookupTableInput {
+    pub address: Option<String>,
+    pub addresses: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+pub struct TransactionBuildInput {
+    pub instructions: Option<Vec<InstructionSpec>>,
+    pub fee_payer: Option<String>,
+    pub recent_blockhash: Option<String>,
+    pub version: Option<String>,
+    pub address_lookup_tables: Option<Vec<AddressLookupTableInput>>,
+    pub nonce_account: Option<String>,
+    pub nonce_authority: Option<String>,
+    pub nonce_value: Option<String>,
+    #[serde(flatten)]
+    pub compute_budget: ComputeBudgetInput,
+}
+
+#[derive(Deserialize)]
+pub struct TransactionSigningInput {
+    pub transaction: Option<String>,
+    pub secrets: Option<Vec<String>>,
+    pub key_ids: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+pub struct KeystoreDeleteInput {
+    pub key_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct KeystoreExportInput {
+    pub key_id: Option<String>,
+    pub passphrase: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TransactionSubmissionInput {
+    pub transaction: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SignatureStatusInput {
+    pub signature: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum InspectAccountInput {
+    Address(String),
+    Meta {
+        pubkey: String,
+        #[serde(default, alias = "isSigner")]
+        is_signer: bool,
+        #[serde(default)]
+        is_writable: bool,
+    },
+}
+
+#[derive(Deserialize)]
+pub struct InspectionInput {
+    pub transaction: Option<String>,
+    pub address_lookup_tables: Option<Vec<AddressLookupTableInput>>,
+    pub program_id: Option<String>,
+    pub accounts: Option<Vec<InspectAccountInput>>,
+    pub instruction_data: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AccountDecodeInput {
+    pub data: Option<String>,
+    pub owner: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum AddressSeedInput {
+    Utf8(String),
+    Pubkey(String),
+    #[serde(deserialize_with = "crate::utils::parse_amount_field")]
+    U64(Option<u64>),
+    Base64(String),
+}
+
+#[derive(Deserialize)]
+pub struct FindProgramAddressInput {
+    pub program_id: Option<String>,
+    pub seeds: Option<Vec<AddressSeedInput>>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateProgramAddressInput {
+    pub program_id: Option<String>,
+    pub seeds: Option<Vec<AddressSeedInput>>,
+    pub bump: Option<u8>,
+}
+
+#[derive(Deserialize)]
+pub struct SeedAddressInput {
+    pub base: Option<String>,
+    pub seed: Option<String>,
+    pub owner: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AssociatedTokenAddressInput {
+    pub owner: Option<String>,
+    pub mint: Option<String>,
+    pub token_program: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SolanaPayUrlInput {
+    pub recipient: Option<String>,
+    pub amount: Option<String>,
+    pub decimals: Option<u8>,
+    pub spl_token: Option<String>,
+    pub references: Option<Vec<String>>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub memo: Option<String>,
+    pub qr_format: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SolanaPayParseInput {
+    pub url: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+#[derive(Deserialize)]
+pub struct SolanaPayAccountInput {
+    pub account: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SiwsChallengeInput {
+    pub domain: Option<String>,
+    pub address: Option<String>,
+    pub statement: Option<String>,
+    pub uri: Option<String>,
+    pub chain_id: Option<String>,
+    pub ttl_seconds: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct SiwsVerificationInput {
+    pub message: Option<String>,
+    pub signature: Option<String>,
+}
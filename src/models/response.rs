@@ -1,123 +1,349 @@
+use base64::{engine::general_purpose, Engine as _};
 use serde::Serialize;
+use solana_sdk::instruction::Instruction;
 
 #[derive(Serialize)]
-pub struct SuccessResponse<T> {
-    pub success: bool,
-    pub data: T,
+pub struct KeypairOutput {
+    pub pubkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct DerivedKeypairOutput {
+    pub derivation_path: String,
+    #[serde(flatten)]
+    pub keypair: KeypairOutput,
 }
 
 #[derive(Serialize)]
-pub struct ErrorResponse {
-    pub success: bool,
-    pub error: String,
+pub struct MnemonicKeypairOutput {
+    pub mnemonic: String,
+    pub accounts: Vec<DerivedKeypairOutput>,
 }
 
 #[derive(Serialize)]
-pub struct KeypairResponse {
+pub struct AccountMetadata {
     pub pubkey: String,
-    pub secret: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
 }
 
 #[derive(Serialize)]
-pub struct CreateTokenResponse {
+pub struct InstructionOutput {
     pub program_id: String,
-    pub accounts: Vec<TokenAccount>,
+    pub accounts: Vec<AccountMetadata>,
     pub instruction_data: String,
 }
 
+impl From<&Instruction> for InstructionOutput {
+    fn from(instruction: &Instruction) -> Self {
+        InstructionOutput {
+            program_id: instruction.program_id.to_string(),
+            accounts: instruction.accounts
+                .iter()
+                .map(|account| AccountMetadata {
+                    pubkey: account.pubkey.to_string(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            instruction_data: general_purpose::STANDARD.encode(&instruction.data),
+        }
+    }
+}
+
 #[derive(Serialize)]
-pub struct TokenAccount {
-    pub pubkey: String,
-    pub is_signer: bool,
-    pub is_writable: bool,
+pub struct FeeEstimateOutput {
+    pub signatures: usize,
+    pub compute_unit_limit: u32,
+    pub priority_fee_micro_lamports: u64,
+    pub base_fee_lamports: u64,
+    pub priority_fee_lamports: u64,
+    pub total_lamports: u64,
 }
 
 #[derive(Serialize)]
-pub struct MintTokenResponse {
-    pub program_id: String,
-    pub accounts: Vec<TokenAccount>,
-    pub instruction_data: String,
+pub struct ComputeBudgetedOutput<T: Serialize> {
+    #[serde(flatten)]
+    pub output: T,
+    pub instructions: Vec<InstructionOutput>,
+    pub fee_estimate: FeeEstimateOutput,
+}
+
+#[derive(Serialize)]
+pub struct AssociatedTokenAccountOutput {
+    pub address: String,
+    pub instruction: InstructionOutput,
+}
+
+#[derive(Serialize)]
+pub struct MintCreationOutput {
+    pub space: usize,
+    pub lamports: u64,
 }
 
 #[derive(Serialize)]
-pub struct SignMessageResponse {
+pub struct SignatureOutput {
     pub signature: String,
     pub public_key: String,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_format: Option<String>,
 }
 
 #[derive(Serialize)]
-pub struct VerifyMessageResponse {
+pub struct VerificationOutput {
     pub valid: bool,
     pub message: String,
     pub pubkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_format: Option<String>,
 }
 
 #[derive(Serialize)]
-pub struct SendSolResponse {
+pub struct SolTransferOutput {
     pub program_id: String,
     pub accounts: Vec<String>,
     pub instruction_data: String,
 }
 
 #[derive(Serialize)]
-pub struct SendTokenResponse {
+pub struct RentExemptionOutput {
+    pub space: u64,
+    pub lamports: u64,
+}
+
+#[derive(Serialize)]
+pub struct TokenAccountMetadata {
+    pub pubkey: String,
+    #[serde(rename = "isSigner")]
+    pub is_signer: bool,
+}
+
+#[derive(Serialize)]
+pub struct TokenTransferOutput {
     pub program_id: String,
-    pub accounts: Vec<TokenAccount>,
+    pub accounts: Vec<TokenAccountMetadata>,
     pub instruction_data: String,
 }
 
 #[derive(Serialize)]
-pub struct KeypairOutput {
+pub struct LookupTableResolution {
+    pub table: String,
+    pub writable: Vec<String>,
+    pub readonly: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct TransactionBuildOutput {
+    pub transaction: String,
+    pub message: String,
+    pub version: String,
+    pub signers: Vec<String>,
+    pub fee_payer: String,
+    pub recent_blockhash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce_account: Option<String>,
+    pub static_keys: Vec<String>,
+    pub lookup_table_keys: Vec<LookupTableResolution>,
+    pub fee_estimate: FeeEstimateOutput,
+}
+
+#[derive(Serialize)]
+pub struct SignatureSlotOutput {
+    pub pubkey: String,
+    pub signature: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct TransactionSigningOutput {
+    pub transaction: String,
+    pub signatures: Vec<SignatureSlotOutput>,
+    pub missing_signers: Vec<String>,
+    pub fully_signed: bool,
+}
+
+#[derive(Serialize)]
+pub struct KeystoreKeyOutput {
+    pub key_id: String,
+    pub pubkey: String,
+    pub created_at: u64,
+}
+
+#[derive(Serialize)]
+pub struct KeystoreDeleteOutput {
+    pub key_id: String,
+    pub deleted: bool,
+}
+
+#[derive(Serialize)]
+pub struct KeystoreExportOutput {
+    pub key_id: String,
     pub pubkey: String,
     pub secret: String,
 }
 
 #[derive(Serialize)]
-pub struct AccountMetadata {
+pub struct BlockhashOutput {
+    pub blockhash: String,
+    pub last_valid_block_height: u64,
+}
+
+#[derive(Serialize)]
+pub struct TransactionSubmissionOutput {
+    pub signature: String,
+}
+
+#[derive(Serialize)]
+pub struct SignatureStatusOutput {
+    pub signature: String,
+    pub found: bool,
+    pub slot: Option<u64>,
+    pub confirmations: Option<u64>,
+    pub confirmation_status: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct InspectedAccountOutput {
     pub pubkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     pub is_signer: bool,
     pub is_writable: bool,
 }
 
 #[derive(Serialize)]
-pub struct InstructionOutput {
+pub struct InspectedInstructionOutput {
     pub program_id: String,
-    pub accounts: Vec<AccountMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub program: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub instruction_type: Option<String>,
+    pub accounts: Vec<InspectedAccountOutput>,
+    pub fields: serde_json::Map<String, serde_json::Value>,
     pub instruction_data: String,
+    pub unknown_program: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decode_error: Option<String>,
 }
 
 #[derive(Serialize)]
-pub struct SignatureOutput {
-    pub signature: String,
-    pub public_key: String,
-    pub message: String,
+pub struct TransactionInspectionOutput {
+    pub version: String,
+    pub fee_payer: String,
+    pub recent_blockhash: String,
+    pub signatures: Vec<SignatureSlotOutput>,
+    pub account_keys: Vec<String>,
+    pub lookup_table_keys: Vec<LookupTableResolution>,
+    pub instructions: Vec<InspectedInstructionOutput>,
 }
 
 #[derive(Serialize)]
-pub struct VerificationOutput {
-    pub valid: bool,
-    pub message: String,
-    pub pubkey: String,
+pub struct DecodedExtensionOutput {
+    #[serde(rename = "type")]
+    pub extension_type: String,
+    pub fields: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Serialize)]
-pub struct SolTransferOutput {
+pub struct AccountDecodeOutput {
+    pub owner: String,
+    pub program: String,
+    pub account_type: String,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Vec<DecodedExtensionOutput>>,
+}
+
+#[derive(Serialize)]
+pub struct ProgramAddressOutput {
+    pub address: String,
     pub program_id: String,
-    pub accounts: Vec<String>,
-    pub instruction_data: String,
+    pub bump: u8,
+    pub on_curve: bool,
 }
 
 #[derive(Serialize)]
-pub struct TokenAccountMetadata {
-    pub pubkey: String,
-    #[serde(rename = "isSigner")]
-    pub is_signer: bool,
+pub struct SeedAddressOutput {
+    pub address: String,
+    pub base: String,
+    pub seed: String,
+    pub owner: String,
+    pub on_curve: bool,
 }
 
 #[derive(Serialize)]
-pub struct TokenTransferOutput {
-    pub program_id: String,
-    pub accounts: Vec<TokenAccountMetadata>,
-    pub instruction_data: String,
-}
\ No newline at end of file
+pub struct AssociatedTokenAddressOutput {
+    pub token_program: String,
+    pub address: String,
+    pub bump: u8,
+    pub on_curve: bool,
+}
+
+#[derive(Serialize)]
+pub struct AssociatedTokenAddressesOutput {
+    pub owner: String,
+    pub mint: String,
+    pub addresses: Vec<AssociatedTokenAddressOutput>,
+}
+
+#[derive(Serialize)]
+pub struct QrCodeOutput {
+    pub format: String,
+    pub content_type: String,
+    pub data: String,
+}
+
+#[derive(Serialize)]
+pub struct SolanaPayUrlOutput {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qr_code: Option<QrCodeOutput>,
+}
+
+#[derive(Serialize)]
+pub struct SolanaPayTransferRequestOutput {
+    pub recipient: String,
+    pub amount: Option<String>,
+    pub base_amount: Option<u64>,
+    pub spl_token: Option<String>,
+    pub references: Vec<String>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub memo: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SolanaPayMetadataOutput {
+    pub label: String,
+    pub icon: String,
+}
+
+#[derive(Serialize)]
+pub struct SolanaPayTransactionOutput {
+    pub transaction: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    pub references: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct SiwsChallengeOutput {
+    pub message: String,
+    pub nonce: String,
+    pub issued_at: String,
+    pub expiration_time: String,
+}
+
+#[derive(Serialize)]
+pub struct SiwsVerificationOutput {
+    pub address: String,
+    pub domain: String,
+    pub chain_id: String,
+    pub session_token: String,
+    pub expires_at: String,
+}
@@ -0,0 +1,367 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use aes_gcm_siv::aead::{Aead, NewAead, Payload};
+use aes_gcm_siv::{Aes256GcmSiv, Nonce};
+use base64::{engine::general_purpose, Engine as _};
+use hmac::Hmac;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use solana_sdk::signature::{Keypair, Signer};
+use thiserror::Error;
+use crate::utils::crypto::keypair_from_secret;
+
+const KEYSTORE_HEADER_FILE: &str = "keystore.json";
+const KEYSTORE_FORMAT_VERSION: u32 = 1;
+const KDF_ALGORITHM: &str = "pbkdf2-hmac-sha256";
+const KDF_ITERATIONS: u32 = 100_000;
+const PASSPHRASE_CHECK_PLAINTEXT: &[u8] = b"solana-http-server keystore";
+
+#[derive(Debug, Error)]
+pub enum KeystoreError {
+    #[error("Keystore I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Corrupted keystore file: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("Unsupported keystore format")]
+    UnsupportedFormat,
+    #[error("Invalid keystore passphrase")]
+    InvalidPassphrase,
+    #[error("Invalid key id")]
+    InvalidKeyId,
+    #[error("Key not found")]
+    NotFound,
+    #[error("Corrupted key entry")]
+    Corrupted,
+    #[error("Keystore task failed")]
+    TaskFailed,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreHeader {
+    version: u32,
+    kdf: String,
+    iterations: u32,
+    salt: String,
+    check_nonce: String,
+    check_ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreEntryFile {
+    key_id: String,
+    pubkey: String,
+    created_at: u64,
+    nonce: String,
+    ciphertext: String,
+}
+
+pub struct KeystoreEntry {
+    pub key_id: String,
+    pub pubkey: String,
+    pub created_at: u64,
+}
+
+/// File-backed store of ed25519 keypairs, one JSON file per key next to a
+/// `keystore.json` header that holds the KDF salt. Secrets are sealed with
+/// AES-256-GCM-SIV under a key derived from the passphrase given to `open`.
+pub struct Keystore {
+    directory: PathBuf,
+    salt: Vec<u8>,
+    iterations: u32,
+    cipher: Aes256GcmSiv,
+}
+
+impl Keystore {
+    pub fn open(directory: &Path, passphrase: &str) -> Result<Keystore, KeystoreError> {
+        fs::create_dir_all(directory)?;
+        let header_path = directory.join(KEYSTORE_HEADER_FILE);
+        if header_path.exists() {
+            let header: KeystoreHeader = serde_json::from_slice(&fs::read(&header_path)?)?;
+            if header.version != KEYSTORE_FORMAT_VERSION || header.kdf != KDF_ALGORITHM {
+                return Err(KeystoreError::UnsupportedFormat);
+            }
+            let salt = decode_field(&header.salt)?;
+            let cipher = derive_cipher(passphrase, &salt, header.iterations);
+            let check_nonce = decode_field(&header.check_nonce)?;
+            let check_ciphertext = decode_field(&header.check_ciphertext)?;
+            let check_plaintext = open_sealed(&cipher, &check_nonce, &check_ciphertext, KEYSTORE_HEADER_FILE.as_bytes())
+                .map_err(|_| KeystoreError::InvalidPassphrase)?;
+            if check_plaintext != PASSPHRASE_CHECK_PLAINTEXT {
+                return Err(KeystoreError::InvalidPassphrase);
+            }
+            return Ok(Keystore {
+                directory: directory.to_path_buf(),
+                salt,
+                iterations: header.iterations,
+                cipher,
+            });
+        }
+        let mut salt = vec![0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let cipher = derive_cipher(passphrase, &salt, KDF_ITERATIONS);
+        let (check_nonce, check_ciphertext) = seal(&cipher, PASSPHRASE_CHECK_PLAINTEXT, KEYSTORE_HEADER_FILE.as_bytes())?;
+        let header = KeystoreHeader {
+            version: KEYSTORE_FORMAT_VERSION,
+            kdf: KDF_ALGORITHM.to_string(),
+            iterations: KDF_ITERATIONS,
+            salt: general_purpose::STANDARD.encode(&salt),
+            check_nonce: general_purpose::STANDARD.encode(check_nonce),
+            check_ciphertext: general_purpose::STANDARD.encode(check_ciphertext),
+        };
+        write_atomically(&header_path, &serde_json::to_vec_pretty(&header)?)?;
+        Ok(Keystore {
+            directory: directory.to_path_buf(),
+            salt,
+            iterations: KDF_ITERATIONS,
+            cipher,
+        })
+    }
+
+    pub fn store(&self, keypair: &Keypair) -> Result<KeystoreEntry, KeystoreError> {
+        let key_id = format!("{:032x}", rand::random::<u128>());
+        let (nonce, ciphertext) = seal(&self.cipher, &keypair.to_bytes(), key_id.as_bytes())?;
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let entry_file = KeystoreEntryFile {
+            key_id: key_id.clone(),
+            pubkey: keypair.pubkey().to_string(),
+            created_at,
+            nonce: general_purpose::STANDARD.encode(nonce),
+            ciphertext: general_purpose::STANDARD.encode(ciphertext),
+        };
+        write_atomically(&self.entry_path(&key_id)?, &serde_json::to_vec_pretty(&entry_file)?)?;
+        Ok(KeystoreEntry {
+            key_id,
+            pubkey: entry_file.pubkey,
+            created_at,
+        })
+    }
+
+    pub fn list(&self) -> Result<Vec<KeystoreEntry>, KeystoreError> {
+        let mut entries = Vec::new();
+        for dir_entry in fs::read_dir(&self.directory)? {
+            let path = dir_entry?.path();
+            let is_entry_file = path.extension().map(|ext| ext == "json").unwrap_or(false)
+                && path.file_name().map(|name| name != KEYSTORE_HEADER_FILE).unwrap_or(false);
+            if !is_entry_file {
+                continue;
+            }
+            let entry_file: KeystoreEntryFile = serde_json::from_slice(&fs::read(&path)?)?;
+            entries.push(KeystoreEntry {
+                key_id: entry_file.key_id,
+                pubkey: entry_file.pubkey,
+                created_at: entry_file.created_at,
+            });
+        }
+        entries.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.key_id.cmp(&b.key_id)));
+        Ok(entries)
+    }
+
+    pub fn load(&self, key_id: &str) -> Result<Keypair, KeystoreError> {
+        let entry_file = self.read_entry(key_id)?;
+        let nonce = decode_field(&entry_file.nonce)?;
+        let ciphertext = decode_field(&entry_file.ciphertext)?;
+        let secret_bytes = open_sealed(&self.cipher, &nonce, &ciphertext, entry_file.key_id.as_bytes())?;
+        let keypair = Keypair::from_bytes(&secret_bytes).map_err(|_| KeystoreError::Corrupted)?;
+        if keypair.pubkey().to_string() != entry_file.pubkey {
+            return Err(KeystoreError::Corrupted);
+        }
+        Ok(keypair)
+    }
+
+    pub fn export(&self, key_id: &str, passphrase: &str) -> Result<Keypair, KeystoreError> {
+        let cipher = derive_cipher(passphrase, &self.salt, self.iterations);
+        let entry_file = self.read_entry(key_id)?;
+        let nonce = decode_field(&entry_file.nonce)?;
+        let ciphertext = decode_field(&entry_file.ciphertext)?;
+        open_sealed(&cipher, &nonce, &ciphertext, entry_file.key_id.as_bytes())
+            .map_err(|_| KeystoreError::InvalidPassphrase)?;
+        self.load(key_id)
+    }
+
+    pub fn delete(&self, key_id: &str) -> Result<(), KeystoreError> {
+        let path = self.entry_path(key_id)?;
+        if !path.exists() {
+            return Err(KeystoreError::NotFound);
+        }
+        fs::remove_file(path)?;
+        Ok(())
+    }
+
+    fn read_entry(&self, key_id: &str) -> Result<KeystoreEntryFile, KeystoreError> {
+        let path = self.entry_path(key_id)?;
+        if !path.exists() {
+            return Err(KeystoreError::NotFound);
+        }
+        let entry_file: KeystoreEntryFile = serde_json::from_slice(&fs::read(path)?)?;
+        if entry_file.key_id != key_id {
+            return Err(KeystoreError::Corrupted);
+        }
+        Ok(entry_file)
+    }
+
+    fn entry_path(&self, key_id: &str) -> Result<PathBuf, KeystoreError> {
+        let is_valid_id = key_id.len() == 32 && key_id.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase());
+        if !is_valid_id {
+            return Err(KeystoreError::InvalidKeyId);
+        }
+        Ok(self.directory.join(format!("{}.json", key_id)))
+    }
+}
+
+/// Runs a keystore operation on the blocking pool. Every operation touches the
+/// filesystem and `export` re-runs the KDF, neither of which may stall the
+/// async worker serving the request.
+pub async fn run_blocking<T, F>(keystore: Arc<Keystore>, operation: F) -> Result<T, KeystoreError>
+where
+    F: FnOnce(&Keystore) -> Result<T, KeystoreError> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || operation(&keystore))
+        .await
+        .map_err(|_| KeystoreError::TaskFailed)?
+}
+
+pub async fn resolve_keypair(
+    keystore: Option<&Arc<Keystore>>,
+    secret: Option<&str>,
+    key_id: Option<&str>,
+) -> Result<Keypair, String> {
+    let secret = secret.map(str::trim).filter(|s| !s.is_empty());
+    let key_id = key_id.map(str::trim).filter(|s| !s.is_empty());
+    match (secret, key_id) {
+        (Some(_), Some(_)) => Err("Provide either secret or key_id, not both".to_string()),
+        (Some(secret_key), None) => keypair_from_secret(secret_key),
+        (None, Some(id)) => match keystore {
+            Some(keystore) => {
+                let key_id = id.to_string();
+                run_blocking(keystore.clone(), move |keystore| keystore.load(&key_id))
+                    .await
+                    .map_err(|error| error.to_string())
+            },
+            None => Err("Keystore is not configured".to_string()),
+        },
+        (None, None) => Err("Missing required fields".to_string()),
+    }
+}
+
+fn derive_cipher(passphrase: &str, salt: &[u8], iterations: u32) -> Aes256GcmSiv {
+    let mut derived_key = [0u8; 32];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), salt, iterations, &mut derived_key);
+    Aes256GcmSiv::new(&derived_key.into())
+}
+
+fn seal(cipher: &Aes256GcmSiv, plaintext: &[u8], aad: &[u8]) -> Result<([u8; 12], Vec<u8>), KeystoreError> {
+    let mut nonce = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(&Nonce::from(nonce), Payload { msg: plaintext, aad })
+        .map_err(|_| KeystoreError::Corrupted)?;
+    Ok((nonce, ciphertext))
+}
+
+fn open_sealed(cipher: &Aes256GcmSiv, nonce: &[u8], ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, KeystoreError> {
+    let nonce: [u8; 12] = nonce.try_into().map_err(|_| KeystoreError::Corrupted)?;
+    cipher
+        .decrypt(&Nonce::from(nonce), Payload { msg: ciphertext, aad })
+        .map_err(|_| KeystoreError::Corrupted)
+}
+
+fn decode_field(value: &str) -> Result<Vec<u8>, KeystoreError> {
+    general_purpose::STANDARD.decode(value).map_err(|_| KeystoreError::Corrupted)
+}
+
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), KeystoreError> {
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSPHRASE: &str = "correct horse battery staple";
+
+    #[test]
+    fn stored_keypair_round_trips() {
+        let directory = tempfile::tempdir().unwrap();
+        let keystore = Keystore::open(directory.path(), PASSPHRASE).unwrap();
+        let keypair = Keypair::new();
+        let entry = keystore.store(&keypair).unwrap();
+        assert_eq!(entry.pubkey, keypair.pubkey().to_string());
+        assert_eq!(keystore.load(&entry.key_id).unwrap().to_bytes(), keypair.to_bytes());
+
+        let reopened = Keystore::open(directory.path(), PASSPHRASE).unwrap();
+        assert_eq!(reopened.load(&entry.key_id).unwrap().to_bytes(), keypair.to_bytes());
+    }
+
+    #[test]
+    fn list_returns_stored_entries_without_header() {
+        let directory = tempfile::tempdir().unwrap();
+        let keystore = Keystore::open(directory.path(), PASSPHRASE).unwrap();
+        let first = keystore.store(&Keypair::new()).unwrap();
+        let second = keystore.store(&Keypair::new()).unwrap();
+        let mut listed: Vec<String> = keystore.list().unwrap().into_iter().map(|entry| entry.key_id).collect();
+        listed.sort();
+        let mut expected = vec![first.key_id, second.key_id];
+        expected.sort();
+        assert_eq!(listed, expected);
+    }
+
+    #[test]
+    fn delete_removes_entry() {
+        let directory = tempfile::tempdir().unwrap();
+        let keystore = Keystore::open(directory.path(), PASSPHRASE).unwrap();
+        let entry = keystore.store(&Keypair::new()).unwrap();
+        keystore.delete(&entry.key_id).unwrap();
+        assert!(keystore.list().unwrap().is_empty());
+        assert!(matches!(keystore.load(&entry.key_id), Err(KeystoreError::NotFound)));
+        assert!(matches!(keystore.delete(&entry.key_id), Err(KeystoreError::NotFound)));
+    }
+
+    #[test]
+    fn export_requires_the_keystore_passphrase() {
+        let directory = tempfile::tempdir().unwrap();
+        let keystore = Keystore::open(directory.path(), PASSPHRASE).unwrap();
+        let keypair = Keypair::new();
+        let entry = keystore.store(&keypair).unwrap();
+        assert_eq!(keystore.export(&entry.key_id, PASSPHRASE).unwrap().to_bytes(), keypair.to_bytes());
+        assert!(matches!(keystore.export(&entry.key_id, "wrong"), Err(KeystoreError::InvalidPassphrase)));
+    }
+
+    #[test]
+    fn open_rejects_wrong_passphrase() {
+        let directory = tempfile::tempdir().unwrap();
+        Keystore::open(directory.path(), PASSPHRASE).unwrap();
+        assert!(matches!(Keystore::open(directory.path(), "wrong"), Err(KeystoreError::InvalidPassphrase)));
+    }
+
+    #[test]
+    fn invalid_key_ids_are_rejected() {
+        let directory = tempfile::tempdir().unwrap();
+        let keystore = Keystore::open(directory.path(), PASSPHRASE).unwrap();
+        for key_id in ["../keystore", "keystore", "ABCDEF0123456789ABCDEF0123456789", "0123"] {
+            assert!(matches!(keystore.load(key_id), Err(KeystoreError::InvalidKeyId)));
+            assert!(matches!(keystore.delete(key_id), Err(KeystoreError::InvalidKeyId)));
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_keypair_loads_by_key_id() {
+        let directory = tempfile::tempdir().unwrap();
+        let keystore = Arc::new(Keystore::open(directory.path(), PASSPHRASE).unwrap());
+        let keypair = Keypair::new();
+        let entry = keystore.store(&keypair).unwrap();
+        let resolved = resolve_keypair(Some(&keystore), None, Some(&entry.key_id)).await.unwrap();
+        assert_eq!(resolved.pubkey(), keypair.pubkey());
+        assert!(resolve_keypair(Some(&keystore), Some("secret"), Some(&entry.key_id)).await.is_err());
+        assert!(resolve_keypair(None, None, Some(&entry.key_id)).await.is_err());
+    }
+}
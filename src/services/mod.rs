@@ -0,0 +1,4 @@
+pub mod keystore;
+pub mod rpc;
+pub mod pay_templates;
+pub mod siws;
\ No newline at end of file
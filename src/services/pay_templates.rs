@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use serde::Deserialize;
+use thiserror::Error;
+use crate::utils::validation::{
+    parse_ui_amount, validate_memo, validate_pubkey_format, validate_token_decimals, SOL_DECIMALS,
+};
+
+#[derive(Debug, Error)]
+pub enum PayTemplateError {
+    #[error("Template file I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Malformed template file: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("Invalid template {name}: {reason}")]
+    Invalid { name: String, reason: String },
+}
+
+/// One merchant payment a Solana Pay wallet can request a transaction for.
+/// `amount` is in SOL, or in whole tokens when `spl_token` is set.
+#[derive(Deserialize)]
+pub struct PayTemplate {
+    pub label: String,
+    pub icon: String,
+    pub recipient: String,
+    pub amount: Option<String>,
+    pub spl_token: Option<String>,
+    pub decimals: Option<u8>,
+    pub token_program: Option<String>,
+    pub memo: Option<String>,
+    pub message: Option<String>,
+    #[serde(default)]
+    pub references: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct PayTemplateFile {
+    templates: HashMap<String, PayTemplate>,
+}
+
+pub struct PayTemplates {
+    templates: HashMap<String, PayTemplate>,
+}
+
+impl PayTemplates {
+    pub fn load(path: &Path) -> Result<PayTemplates, PayTemplateError> {
+        let template_file: PayTemplateFile = serde_json::from_slice(&fs::read(path)?)?;
+        for (name, template) in &template_file.templates {
+            validate_template(template).map_err(|reason| PayTemplateError::Invalid {
+                name: name.clone(),
+                reason,
+            })?;
+        }
+        Ok(PayTemplates {
+            templates: template_file.templates,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PayTemplate> {
+        self.templates.get(name)
+    }
+}
+
+fn validate_template(template: &PayTemplate) -> Result<(), String> {
+    if template.label.trim().is_empty() || template.icon.trim().is_empty() {
+        return Err("label and icon are required".to_string());
+    }
+    validate_pubkey_format(&template.recipient, "recipient")?;
+    let amount_decimals = match template.spl_token {
+        Some(ref mint) => {
+            validate_pubkey_format(mint, "spl-token")?;
+            let decimals = template.decimals.ok_or_else(|| "decimals are required for spl-token templates".to_string())?;
+            validate_token_decimals(decimals)?;
+            decimals
+        },
+        None => SOL_DECIMALS,
+    };
+    if let Some(ref amount) = template.amount {
+        parse_ui_amount(amount, amount_decimals).map_err(|error_msg| error_msg.replacen("ui_amount", "amount", 1))?;
+    }
+    for reference in &template.references {
+        validate_pubkey_format(reference, "reference")?;
+    }
+    if let Some(ref memo) = template.memo {
+        validate_memo(memo)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use solana_sdk::pubkey::Pubkey;
+
+    fn load_templates(templates: serde_json::Value) -> Result<PayTemplates, PayTemplateError> {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), json!({ "templates": templates }).to_string()).unwrap();
+        PayTemplates::load(file.path())
+    }
+
+    fn invalid_reason(templates: serde_json::Value) -> String {
+        match load_templates(templates) {
+            Err(PayTemplateError::Invalid { reason, .. }) => reason,
+            Err(error) => panic!("unexpected error: {}", error),
+            Ok(_) => panic!("template should have been rejected"),
+        }
+    }
+
+    #[test]
+    fn loads_templates_from_file() {
+        let recipient = Pubkey::new_unique().to_string();
+        let templates = load_templates(json!({
+            "coffee": {
+                "label": "Coffee",
+                "icon": "https://example.com/icon.svg",
+                "recipient": recipient,
+                "amount": "0.000000001",
+                "references": [Pubkey::new_unique().to_string()],
+            },
+            "usdc": {
+                "label": "Coffee (USDC)",
+                "icon": "https://example.com/icon.svg",
+                "recipient": recipient,
+                "amount": "2.50",
+                "spl_token": Pubkey::new_unique().to_string(),
+                "decimals": 6,
+            },
+        }))
+        .unwrap();
+        assert_eq!(templates.get("coffee").unwrap().recipient, recipient);
+        assert_eq!(templates.get("usdc").unwrap().decimals, Some(6));
+        assert!(templates.get("tea").is_none());
+    }
+
+    #[test]
+    fn rejects_amounts_beyond_template_precision() {
+        let template = |amount: &str, spl_token: Option<String>, decimals: Option<u8>| json!({
+            "pay": {
+                "label": "Pay",
+                "icon": "https://example.com/icon.svg",
+                "recipient": Pubkey::new_unique().to_string(),
+                "amount": amount,
+                "spl_token": spl_token,
+                "decimals": decimals,
+            },
+        });
+        assert_eq!(
+            invalid_reason(template("0.0000000001", None, None)),
+            "Invalid amount - at most 9 decimal places allowed",
+        );
+        assert_eq!(
+            invalid_reason(template("1.001", Some(Pubkey::new_unique().to_string()), Some(2))),
+            "Invalid amount - at most 2 decimal places allowed",
+        );
+        assert_eq!(invalid_reason(template("ten", None, None)), "Invalid amount format");
+        assert_eq!(
+            invalid_reason(template("1", Some(Pubkey::new_unique().to_string()), None)),
+            "decimals are required for spl-token templates",
+        );
+    }
+
+    #[test]
+    fn reports_malformed_files() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), "not json").unwrap();
+        assert!(matches!(PayTemplates::load(file.path()), Err(PayTemplateError::Format(_))));
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use serde_json::{json, Value};
+use solana_sdk::hash::{hashv, Hash};
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::program_utils::limited_deserialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::VersionedTransaction;
+use thiserror::Error;
+
+const MOCK_BLOCKHASH_QUEUE_LENGTH: usize = 150;
+const MOCK_FINALIZATION_DEPTH: u64 = 32;
+const RPC_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum RpcError {
+    #[error("RPC transport error: {0}")]
+    Transport(String),
+    #[error("RPC error {code}: {message}")]
+    Rpc { code: i64, message: String },
+    #[error("Invalid RPC response: {0}")]
+    InvalidResponse(String),
+    #[error("Transaction rejected: {0}")]
+    Rejected(String),
+}
+
+pub struct BlockhashInfo {
+    pub blockhash: Hash,
+    pub last_valid_block_height: u64,
+}
+
+pub struct SignatureStatusInfo {
+    pub slot: u64,
+    pub confirmations: Option<u64>,
+    pub confirmation_status: Option<String>,
+    pub error: Option<String>,
+}
+
+#[async_trait]
+pub trait RpcBackend: Send + Sync {
+    async fn get_latest_blockhash(&self) -> Result<BlockhashInfo, RpcError>;
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, RpcError>;
+    async fn get_signature_status(&self, signature: &Signature) -> Result<Option<SignatureStatusInfo>, RpcError>;
+}
+
+pub struct HttpRpcBackend {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl HttpRpcBackend {
+    pub fn new(url: &str) -> HttpRpcBackend {
+        HttpRpcBackend {
+            url: url.to_string(),
+            client: reqwest::Client::builder()
+                .timeout(RPC_REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build RPC HTTP client"),
+        }
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        let request_body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self.client
+            .post(&self.url)
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|error| RpcError::Transport(error.to_string()))?
+            .json()
+            .await
+            .map_err(|error| RpcError::InvalidResponse(error.to_string()))?;
+        if let Some(error) = response.get("error") {
+            return Err(RpcError::Rpc {
+                code: error["code"].as_i64().unwrap_or_default(),
+                message: error["message"].as_str().unwrap_or("unknown error").to_string(),
+            });
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| RpcError::InvalidResponse("missing result".to_string()))
+    }
+}
+
+#[async_trait]
+impl RpcBackend for HttpRpcBackend {
+    async fn get_latest_blockhash(&self) -> Result<BlockhashInfo, RpcError> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }])).await?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .and_then(|hash| Hash::from_str(hash).ok())
+            .ok_or_else(|| RpcError::InvalidResponse("missing blockhash".to_string()))?;
+        let last_valid_block_height = result["value"]["lastValidBlockHeight"]
+            .as_u64()
+            .ok_or_else(|| RpcError::InvalidResponse("missing lastValidBlockHeight".to_string()))?;
+        Ok(BlockhashInfo {
+            blockhash,
+            last_valid_block_height,
+        })
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, RpcError> {
+        let transaction_bytes = bincode::serialize(transaction)
+            .map_err(|error| RpcError::Rejected(error.to_string()))?;
+        let encoded_transaction = general_purpose::STANDARD.encode(transaction_bytes);
+        let result = self
+            .call(
+                "sendTransaction",
+                json!([encoded_transaction, { "encoding": "base64", "preflightCommitment": "confirmed" }]),
+            )
+            .await?;
+        result
+            .as_str()
+            .and_then(|signature| Signature::from_str(signature).ok())
+            .ok_or_else(|| RpcError::InvalidResponse("missing transaction signature".to_string()))
+    }
+
+    async fn get_signature_status(&self, signature: &Signature) -> Result<Option<SignatureStatusInfo>, RpcError> {
+        let result = self
+            .call(
+                "getSignatureStatuses",
+                json!([[signature.to_string()], { "searchTransactionHistory": true }]),
+            )
+            .await?;
+        let status = &result["value"][0];
+        if status.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(SignatureStatusInfo {
+            slot: status["slot"].as_u64().unwrap_or_default(),
+            confirmations: status["confirmations"].as_u64(),
+            confirmation_status: status["confirmationStatus"].as_str().map(str::to_string),
+            error: (!status["err"].is_null()).then(|| status["err"].to_string()),
+        }))
+    }
+}
+
+struct MockLedger {
+    slot: u64,
+    recent_blockhashes: VecDeque<Hash>,
+    processed_transactions: HashMap<Signature, u64>,
+    consumed_nonces: HashSet<(Pubkey, Hash)>,
+}
+
+impl MockLedger {
+    fn advance_slot(&mut self) -> Hash {
+        self.slot += 1;
+        let previous_hash = self.recent_blockhashes.back().copied().unwrap_or_default();
+        let next_hash = hashv(&[previous_hash.as_ref(), &self.slot.to_le_bytes()]);
+        self.recent_blockhashes.push_back(next_hash);
+        if self.recent_blockhashes.len() > MOCK_BLOCKHASH_QUEUE_LENGTH {
+            self.recent_blockhashes.pop_front();
+        }
+        next_hash
+    }
+}
+
+/// Returns the nonce account when the message opens with `AdvanceNonceAccount`,
+/// which is how a transaction opts into a durable-nonce lifetime.
+fn durable_nonce_account(message: &VersionedMessage) -> Option<Pubkey> {
+    let account_keys = message.static_account_keys();
+    let first_instruction = message.instructions().first()?;
+    let is_advance_nonce = account_keys.get(first_instruction.program_id_index as usize) == Some(&system_program::ID)
+        && matches!(
+            limited_deserialize(&first_instruction.data),
+            Ok(SystemInstruction::AdvanceNonceAccount)
+        );
+    if !is_advance_nonce {
+        return None;
+    }
+    let nonce_index = *first_instruction.accounts.first()? as usize;
+    message.is_maybe_writable(nonce_index).then(|| account_keys.get(nonce_index).copied()).flatten()
+}
+
+/// In-memory stand-in for a validator. Every call advances the slot by one,
+/// so polling a submitted signature walks it from processed through
+/// confirmed to finalized without any network access. The mock holds no
+/// account state, so a durable-nonce transaction is accepted with any nonce
+/// value that has not already been consumed for its nonce account.
+pub struct MockRpcBackend {
+    ledger: Mutex<MockLedger>,
+}
+
+impl MockRpcBackend {
+    pub fn new() -> MockRpcBackend {
+        let mut ledger = MockLedger {
+            slot: 0,
+            recent_blockhashes: VecDeque::with_capacity(MOCK_BLOCKHASH_QUEUE_LENGTH),
+            processed_transactions: HashMap::new(),
+            consumed_nonces: HashSet::new(),
+        };
+        ledger.advance_slot();
+        MockRpcBackend {
+            ledger: Mutex::new(ledger),
+        }
+    }
+}
+
+impl Default for MockRpcBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl RpcBackend for MockRpcBackend {
+    async fn get_latest_blockhash(&self) -> Result<BlockhashInfo, RpcError> {
+        let mut ledger = self.ledger.lock().unwrap();
+        let blockhash = ledger.advance_slot();
+        Ok(BlockhashInfo {
+            blockhash,
+            last_valid_block_height: ledger.slot + MOCK_BLOCKHASH_QUEUE_LENGTH as u64,
+        })
+    }
+
+    async fn send_transaction(&self, transaction: &VersionedTransaction) -> Result<Signature, RpcError> {
+        if transaction.verify_with_results().iter().any(|valid| !valid) {
+            return Err(RpcError::Rejected("Transaction signature verification failure".to_string()));
+        }
+        let mut ledger = self.ledger.lock().unwrap();
+        let signature = transaction.signatures[0];
+        if ledger.processed_transactions.contains_key(&signature) {
+            return Err(RpcError::Rejected("This transaction has already been processed".to_string()));
+        }
+        let recent_blockhash = *transaction.message.recent_blockhash();
+        let nonce_account = durable_nonce_account(&transaction.message);
+        let has_valid_lifetime = match nonce_account {
+            Some(nonce_account) => !ledger.consumed_nonces.contains(&(nonce_account, recent_blockhash)),
+            None => ledger.recent_blockhashes.contains(&recent_blockhash),
+        };
+        if !has_valid_lifetime {
+            return Err(RpcError::Rejected("Blockhash not found".to_string()));
+        }
+        if let Some(nonce_account) = nonce_account {
+            ledger.consumed_nonces.insert((nonce_account, recent_blockhash));
+        }
+        let current_slot = ledger.slot;
+        ledger.processed_transactions.insert(signature, current_slot);
+        Ok(signature)
+    }
+
+    async fn get_signature_status(&self, signature: &Signature) -> Result<Option<SignatureStatusInfo>, RpcError> {
+        let mut ledger = self.ledger.lock().unwrap();
+        ledger.advance_slot();
+        let processed_slot = match ledger.processed_transactions.get(signature) {
+            Some(slot) => *slot,
+            None => return Ok(None),
+        };
+        let depth = ledger.slot - processed_slot;
+        let (confirmations, confirmation_status) = match depth {
+            0 | 1 => (Some(depth), "processed"),
+            d if d < MOCK_FINALIZATION_DEPTH => (Some(d), "confirmed"),
+            _ => (None, "finalized"),
+        };
+        Ok(Some(SignatureStatusInfo {
+            slot: processed_slot,
+            confirmations,
+            confirmation_status: Some(confirmation_status.to_string()),
+            error: None,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::system_instruction;
+    use crate::handlers::transaction::{
+        compile_transaction, decode_transaction, resolve_transaction_lifetime, sign_transaction, TransactionVersion,
+    };
+
+    fn build_signed_transfer(
+        payer: &Keypair,
+        recent_blockhash: Option<&str>,
+        nonce: Option<(&Pubkey, &Hash)>,
+        version: TransactionVersion,
+    ) -> VersionedTransaction {
+        let (nonce_account, nonce_value) = match nonce {
+            Some((account, value)) => (Some(account.to_string()), Some(value.to_string())),
+            None => (None, None),
+        };
+        let lifetime = resolve_transaction_lifetime(
+            recent_blockhash,
+            nonce_account.as_deref(),
+            nonce_value.as_deref(),
+            None,
+            &payer.pubkey(),
+        )
+        .unwrap();
+        let mut instructions = vec![system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1_000)];
+        if let Some(advance_instruction) = lifetime.advance_nonce {
+            instructions.insert(0, advance_instruction);
+        }
+        let build_output = compile_transaction(&instructions, &payer.pubkey(), &lifetime.blockhash, version, &[]).unwrap();
+        let mut transaction = decode_transaction(&build_output.transaction).unwrap();
+        sign_transaction(&mut transaction, &[payer.insecure_clone()]).unwrap();
+        transaction
+    }
+
+    #[tokio::test]
+    async fn build_sign_send_and_poll_until_finalized() {
+        let rpc_backend = MockRpcBackend::new();
+        let payer = Keypair::new();
+        for version in [TransactionVersion::Legacy, TransactionVersion::V0] {
+            let blockhash = rpc_backend.get_latest_blockhash().await.unwrap().blockhash.to_string();
+            let transaction = build_signed_transfer(&payer, Some(&blockhash), None, version);
+            let signature = rpc_backend.send_transaction(&transaction).await.unwrap();
+            assert_eq!(signature, transaction.signatures[0]);
+
+            let mut observed_statuses = Vec::new();
+            loop {
+                let status = rpc_backend.get_signature_status(&signature).await.unwrap().unwrap();
+                let confirmation_status = status.confirmation_status.unwrap();
+                if observed_statuses.last() != Some(&confirmation_status) {
+                    observed_statuses.push(confirmation_status.clone());
+                }
+                if confirmation_status == "finalized" {
+                    assert_eq!(status.confirmations, None);
+                    break;
+                }
+            }
+            assert_eq!(observed_statuses, ["processed", "confirmed", "finalized"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn send_rejects_unknown_blockhash_duplicates_and_bad_signatures() {
+        let rpc_backend = MockRpcBackend::new();
+        let payer = Keypair::new();
+        let stale_transaction = build_signed_transfer(&payer, Some(&Hash::new_unique().to_string()), None, TransactionVersion::Legacy);
+        assert!(matches!(rpc_backend.send_transaction(&stale_transaction).await, Err(RpcError::Rejected(_))));
+
+        let blockhash = rpc_backend.get_latest_blockhash().await.unwrap().blockhash.to_string();
+        let transaction = build_signed_transfer(&payer, Some(&blockhash), None, TransactionVersion::Legacy);
+        rpc_backend.send_transaction(&transaction).await.unwrap();
+        assert!(matches!(rpc_backend.send_transaction(&transaction).await, Err(RpcError::Rejected(_))));
+
+        let mut forged_transaction = build_signed_transfer(&payer, Some(&blockhash), None, TransactionVersion::Legacy);
+        forged_transaction.signatures[0] = Signature::new_unique();
+        assert!(matches!(rpc_backend.send_transaction(&forged_transaction).await, Err(RpcError::Rejected(_))));
+
+        assert!(rpc_backend.get_signature_status(&Signature::new_unique()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn send_accepts_durable_nonce_lifetime_once() {
+        let rpc_backend = MockRpcBackend::new();
+        let payer = Keypair::new();
+        let nonce_account = Pubkey::new_unique();
+        let nonce_value = Hash::new_unique();
+        let transaction = build_signed_transfer(&payer, None, Some((&nonce_account, &nonce_value)), TransactionVersion::Legacy);
+        assert_eq!(durable_nonce_account(&transaction.message), Some(nonce_account));
+        rpc_backend.send_transaction(&transaction).await.unwrap();
+
+        let replayed_nonce = build_signed_transfer(&payer, None, Some((&nonce_account, &nonce_value)), TransactionVersion::Legacy);
+        assert!(matches!(rpc_backend.send_transaction(&replayed_nonce).await, Err(RpcError::Rejected(_))));
+
+        let other_nonce_account = Pubkey::new_unique();
+        let transaction = build_signed_transfer(&payer, None, Some((&other_nonce_account, &nonce_value)), TransactionVersion::V0);
+        rpc_backend.send_transaction(&transaction).await.unwrap();
+    }
+}
@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
+use hmac::{Hmac, Mac};
+use rand::distributions::{Alphanumeric, DistString};
+use rand::RngCore;
+use serde_json::json;
+use sha2::Sha256;
+
+const SIGN_IN_HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+const SIWS_MESSAGE_VERSION: &str = "1";
+const NONCE_LENGTH: usize = 24;
+const SESSION_SECRET_LENGTH: usize = 32;
+const MAX_PENDING_CHALLENGES: usize = 10_000;
+
+/// Structured Sign-In With Solana message, laid out the way wallets render
+/// `signIn` requests: a header, the address, an optional statement and a
+/// block of `Key: value` fields.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SiwsMessage {
+    pub domain: String,
+    pub address: String,
+    pub statement: Option<String>,
+    pub uri: Option<String>,
+    pub version: String,
+    pub chain_id: String,
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub expiration_time: DateTime<Utc>,
+}
+
+impl SiwsMessage {
+    pub fn new(
+        domain: &str,
+        address: &str,
+        statement: Option<&str>,
+        uri: Option<&str>,
+        chain_id: &str,
+        issued_at: DateTime<Utc>,
+        expiration_time: DateTime<Utc>,
+    ) -> SiwsMessage {
+        SiwsMessage {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            statement: statement.map(str::to_string),
+            uri: uri.map(str::to_string),
+            version: SIWS_MESSAGE_VERSION.to_string(),
+            chain_id: chain_id.to_string(),
+            nonce: Alphanumeric.sample_string(&mut rand::thread_rng(), NONCE_LENGTH),
+            issued_at: issued_at.trunc_subsecs(3),
+            expiration_time: expiration_time.trunc_subsecs(3),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut message_text = format!("{}{}\n{}", self.domain, SIGN_IN_HEADER_SUFFIX, self.address);
+        if let Some(ref statement) = self.statement {
+            message_text.push_str("\n\n");
+            message_text.push_str(statement);
+        }
+        let mut fields = Vec::new();
+        if let Some(ref uri) = self.uri {
+            fields.push(format!("URI: {}", uri));
+        }
+        fields.push(format!("Version: {}", self.version));
+        fields.push(format!("Chain ID: {}", self.chain_id));
+        fields.push(format!("Nonce: {}", self.nonce));
+        fields.push(format!("Issued At: {}", format_timestamp(&self.issued_at)));
+        fields.push(format!("Expiration Time: {}", format_timestamp(&self.expiration_time)));
+        message_text.push_str("\n\n");
+        message_text.push_str(&fields.join("\n"));
+        message_text
+    }
+
+    pub fn parse(message_text: &str) -> Result<SiwsMessage, String> {
+        let invalid = |reason: &str| format!("Invalid SIWS message - {}", reason);
+        let (header_line, remainder) = message_text.split_once('\n').ok_or_else(|| invalid("missing address"))?;
+        let domain = header_line
+            .strip_suffix(SIGN_IN_HEADER_SUFFIX)
+            .filter(|domain| !domain.is_empty())
+            .ok_or_else(|| invalid("malformed header"))?;
+        let (address, body) = remainder.split_once("\n\n").ok_or_else(|| invalid("missing fields"))?;
+        let (statement, field_block) = match body.split_once("\n\n") {
+            Some((statement, field_block)) => (Some(statement.to_string()), field_block),
+            None => (None, body),
+        };
+        let mut fields: HashMap<&str, &str> = HashMap::new();
+        for line in field_block.lines() {
+            let (key, value) = line.split_once(": ").ok_or_else(|| invalid("malformed field"))?;
+            if fields.insert(key, value).is_some() {
+                return Err(invalid(&format!("duplicate {} field", key)));
+            }
+        }
+        let mut required_field = |key: &str| fields.remove(key).ok_or_else(|| invalid(&format!("missing {} field", key)));
+        let version = required_field("Version")?.to_string();
+        let chain_id = required_field("Chain ID")?.to_string();
+        let nonce = required_field("Nonce")?.to_string();
+        let issued_at = parse_timestamp(required_field("Issued At")?).ok_or_else(|| invalid("malformed Issued At"))?;
+        let expiration_time = parse_timestamp(required_field("Expiration Time")?)
+            .ok_or_else(|| invalid("malformed Expiration Time"))?;
+        let uri = fields.remove("URI").map(str::to_string);
+        if let Some(unknown_key) = fields.keys().next() {
+            return Err(invalid(&format!("unsupported {} field", unknown_key)));
+        }
+        if version != SIWS_MESSAGE_VERSION {
+            return Err(invalid("unsupported version"));
+        }
+        Ok(SiwsMessage {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            statement,
+            uri,
+            version,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+        })
+    }
+}
+
+pub fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+/// Outstanding SIWS challenges keyed by nonce, plus the HMAC secret used to
+/// sign session tokens. Challenges live in memory only, so a restart
+/// invalidates every pending sign-in. Challenges are issued without
+/// authentication, so the number held at once is capped.
+pub struct SiwsStore {
+    pending: Mutex<HashMap<String, SiwsMessage>>,
+    max_pending: usize,
+    session_secret: Vec<u8>,
+}
+
+impl SiwsStore {
+    pub fn new(session_secret: &[u8]) -> SiwsStore {
+        SiwsStore {
+            pending: Mutex::new(HashMap::new()),
+            max_pending: MAX_PENDING_CHALLENGES,
+            session_secret: session_secret.to_vec(),
+        }
+    }
+
+    pub fn insert(&self, challenge: SiwsMessage) -> Result<(), String> {
+        let now = Utc::now();
+        let mut pending = self.pending.lock().unwrap();
+        if pending.len() >= self.max_pending {
+            pending.retain(|_, pending_challenge| pending_challenge.expiration_time > now);
+        }
+        if pending.len() >= self.max_pending {
+            return Err("Too many pending sign-in challenges - try again later".to_string());
+        }
+        pending.insert(challenge.nonce.clone(), challenge);
+        Ok(())
+    }
+
+    /// Consumes the nonce only when the signed message matches the challenge
+    /// that was issued for it, so nobody else can burn a pending nonce.
+    pub fn consume(&self, signed_message: &SiwsMessage, now: DateTime<Utc>) -> Result<(), String> {
+        let mut pending = self.pending.lock().unwrap();
+        let issued_challenge = pending
+            .get(&signed_message.nonce)
+            .ok_or_else(|| "Unknown or already used nonce".to_string())?;
+        if issued_challenge.expiration_time <= now {
+            pending.remove(&signed_message.nonce);
+            return Err("Sign-in challenge has expired".to_string());
+        }
+        if issued_challenge != signed_message {
+            return Err("Signed message does not match the issued challenge".to_string());
+        }
+        pending.remove(&signed_message.nonce);
+        Ok(())
+    }
+
+    /// Issues an HS256 JWT so other services holding the same secret can
+    /// check sessions without calling back into this server.
+    pub fn issue_session_token(&self, signed_message: &SiwsMessage, issued_at: DateTime<Utc>, expires_at: DateTime<Utc>) -> String {
+        let encode_segment = |value: serde_json::Value| general_purpose::URL_SAFE_NO_PAD.encode(value.to_string());
+        let header = encode_segment(json!({ "alg": "HS256", "typ": "JWT" }));
+        let claims = encode_segment(json!({
+            "sub": signed_message.address,
+            "aud": signed_message.domain,
+            "chain_id": signed_message.chain_id,
+            "jti": signed_message.nonce,
+            "iat": issued_at.timestamp(),
+            "exp": expires_at.timestamp(),
+        }));
+        let signing_input = format!("{}.{}", header, claims);
+        let mut token_mac = Hmac::<Sha256>::new_from_slice(&self.session_secret).expect("HMAC accepts keys of any length");
+        token_mac.update(signing_input.as_bytes());
+        let token_signature = general_purpose::URL_SAFE_NO_PAD.encode(token_mac.finalize().into_bytes());
+        format!("{}.{}", signing_input, token_signature)
+    }
+}
+
+impl Default for SiwsStore {
+    fn default() -> Self {
+        let mut session_secret = vec![0u8; SESSION_SECRET_LENGTH];
+        rand::thread_rng().fill_bytes(&mut session_secret);
+        SiwsStore::new(&session_secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const ADDRESS: &str = "mvines9iiHiAbtM2emGd7N7NpPNCcKqtK2bmqwvdJwJ";
+
+    fn challenge(statement: Option<&str>, uri: Option<&str>, ttl_seconds: i64) -> SiwsMessage {
+        let issued_at = Utc::now();
+        SiwsMessage::new("example.com", ADDRESS, statement, uri, "mainnet", issued_at, issued_at + Duration::seconds(ttl_seconds))
+    }
+
+    #[test]
+    fn message_text_round_trips() {
+        for (statement, uri) in [(None, None), (Some("Sign in to Example"), Some("https://example.com/login"))] {
+            let message = challenge(statement, uri, 300);
+            assert_eq!(SiwsMessage::parse(&message.to_text()).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn message_text_follows_wallet_layout() {
+        let message = challenge(Some("Sign in to Example"), Some("https://example.com"), 300);
+        let expected_text = format!(
+            "example.com wants you to sign in with your Solana account:\n{}\n\nSign in to Example\n\nURI: https://example.com\nVersion: 1\nChain ID: mainnet\nNonce: {}\nIssued At: {}\nExpiration Time: {}",
+            ADDRESS,
+            message.nonce,
+            format_timestamp(&message.issued_at),
+            format_timestamp(&message.expiration_time),
+        );
+        assert_eq!(message.to_text(), expected_text);
+        assert_eq!(message.nonce.len(), NONCE_LENGTH);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_messages() {
+        let message_text = challenge(None, None, 300).to_text();
+        let rejected_texts = [
+            message_text.replacen(" wants you", " would like you", 1),
+            message_text.replacen("Version: 1", "Version: 2", 1),
+            message_text.replacen("Nonce: ", "Nonce ", 1),
+            format!("{}\nNonce: again", message_text),
+            format!("{}\nResources: none", message_text),
+            message_text.replacen("Issued At: ", "Issued At: yesterday ", 1),
+            message_text.lines().next().unwrap().to_string(),
+        ];
+        for rejected_text in rejected_texts {
+            assert!(SiwsMessage::parse(&rejected_text).is_err(), "{:?} should be rejected", rejected_text);
+        }
+    }
+
+    #[test]
+    fn consume_is_single_use_and_requires_matching_message() {
+        let store = SiwsStore::default();
+        let issued_challenge = challenge(None, None, 300);
+        store.insert(issued_challenge.clone()).unwrap();
+
+        let mut altered_challenge = issued_challenge.clone();
+        altered_challenge.domain = "attacker.example".to_string();
+        assert!(store.consume(&altered_challenge, Utc::now()).is_err());
+
+        store.consume(&issued_challenge, Utc::now()).unwrap();
+        assert!(store.consume(&issued_challenge, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn consume_rejects_expired_challenges() {
+        let store = SiwsStore::default();
+        let issued_challenge = challenge(None, None, 60);
+        store.insert(issued_challenge.clone()).unwrap();
+        let after_expiry = issued_challenge.expiration_time + Duration::seconds(1);
+        assert_eq!(store.consume(&issued_challenge, after_expiry), Err("Sign-in challenge has expired".to_string()));
+        assert!(store.consume(&issued_challenge, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn insert_rejects_challenges_beyond_capacity() {
+        let store = SiwsStore {
+            max_pending: 2,
+            ..SiwsStore::default()
+        };
+        store.insert(challenge(None, None, 300)).unwrap();
+        store.insert(challenge(None, None, -1)).unwrap();
+        store.insert(challenge(None, None, 300)).unwrap();
+        assert!(store.insert(challenge(None, None, 300)).is_err());
+    }
+
+    #[test]
+    fn session_token_is_hmac_signed() {
+        let store = SiwsStore::new(b"test secret");
+        let message = challenge(None, None, 300);
+        let issued_at = Utc::now();
+        let session_token = store.issue_session_token(&message, issued_at, issued_at + Duration::seconds(60));
+        let (signing_input, token_signature) = session_token.rsplit_once('.').unwrap();
+        let mut token_mac = Hmac::<Sha256>::new_from_slice(b"test secret").unwrap();
+        token_mac.update(signing_input.as_bytes());
+        token_mac.verify_slice(&general_purpose::URL_SAFE_NO_PAD.decode(token_signature).unwrap()).unwrap();
+
+        let claims_segment = signing_input.split('.').nth(1).unwrap();
+        let claims: serde_json::Value =
+            serde_json::from_slice(&general_purpose::URL_SAFE_NO_PAD.decode(claims_segment).unwrap()).unwrap();
+        assert_eq!(claims["sub"], ADDRESS);
+        assert_eq!(claims["aud"], "example.com");
+        assert_eq!(claims["jti"], message.nonce.as_str());
+        assert_eq!(claims["exp"].as_i64().unwrap() - claims["iat"].as_i64().unwrap(), 60);
+    }
+}
@@ -0,0 +1,13 @@
+use std::sync::Arc;
+use crate::services::keystore::Keystore;
+use crate::services::pay_templates::PayTemplates;
+use crate::services::rpc::RpcBackend;
+use crate::services::siws::SiwsStore;
+
+#[derive(Clone, Default)]
+pub struct AppState {
+    pub keystore: Option<Arc<Keystore>>,
+    pub rpc: Option<Arc<dyn RpcBackend>>,
+    pub pay_templates: Option<Arc<PayTemplates>>,
+    pub siws: Arc<SiwsStore>,
+}
@@ -1,38 +1,114 @@
-use solana_sdk::signature::{Keypair, Signer};
-use bs58::{decode as bs58_decode, encode as bs58_encode};
-use base64::Engine;
-
-pub fn generate_keypair() -> (String, String) {
-    let keypair = Keypair::new();
-    let pubkey = bs58_encode(keypair.pubkey().to_bytes()).into_string();
-    let secret = bs58_encode(keypair.to_bytes()).into_string();
-    (pubkey, secret)
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use solana_sdk::derivation_path::DerivationPath;
+use solana_sdk::signature::{keypair_from_seed_and_derivation_path, Keypair};
+
+pub fn keypair_from_secret(secret_key: &str) -> Result<Keypair, String> {
+    let secret_bytes = bs58::decode(secret_key.trim())
+        .into_vec()
+        .map_err(|_| "Invalid secret key format".to_string())?;
+    if secret_bytes.len() != 64 {
+        return Err("Invalid secret key length".to_string());
+    }
+    Keypair::from_bytes(&secret_bytes).map_err(|_| "Invalid secret key".to_string())
 }
 
-pub fn sign_message(message: &str, secret: &str) -> Result<(String, String), &'static str> {
-    let secret_bytes = match bs58_decode(secret).into_vec() {
-        Ok(bytes) => bytes,
-        Err(_) => return Err("Invalid secret key format"),
+pub fn generate_mnemonic(word_count: usize) -> Result<String, String> {
+    let mnemonic_type = match word_count {
+        12 => MnemonicType::Words12,
+        24 => MnemonicType::Words24,
+        _ => return Err("Invalid word count - expected 12 or 24".to_string()),
     };
-    let keypair = Keypair::from_bytes(&secret_bytes).map_err(|_| "Invalid secret key")?;
-    let signature = keypair.sign_message(message.as_bytes());
-    Ok((
-        base64::engine::general_purpose::STANDARD.encode(signature.as_ref()),
-        bs58_encode(keypair.pubkey().to_bytes()).into_string(),
-    ))
+    Ok(Mnemonic::new(mnemonic_type, Language::English).into_phrase())
 }
 
-pub fn verify_message(message: &str, signature: &str, pubkey: &str) -> Result<bool, &'static str> {
-    let signature_bytes = match base64::engine::general_purpose::STANDARD.decode(signature) {
-        Ok(bytes) => bytes,
-        Err(_) => return Err("Invalid signature format"),
-    };
-    let pubkey_bytes = match bs58_decode(pubkey).into_vec() {
-        Ok(bytes) => bytes,
-        Err(_) => return Err("Invalid public key format"),
-    };
-    let pubkey = solana_sdk::pubkey::Pubkey::new(&pubkey_bytes);
-    let signature = solana_sdk::signature::Signature::new(&signature_bytes);
-    Ok(signature.verify(pubkey.as_ref(), message.as_bytes()))
+pub fn seed_from_mnemonic(phrase: &str, passphrase: &str) -> Result<Vec<u8>, String> {
+    let normalized_phrase = phrase.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let mnemonic = Mnemonic::from_phrase(&normalized_phrase, Language::English)
+        .map_err(|_| "Invalid mnemonic phrase".to_string())?;
+    Ok(Seed::new(&mnemonic, passphrase).as_bytes().to_vec())
+}
+
+pub fn parse_derivation_path(path: &str) -> Result<DerivationPath, String> {
+    let trimmed_path = path.trim();
+    let mut segments = trimmed_path.split('/');
+    if segments.next() != Some("m") {
+        return Err("Invalid derivation path - must start with m/".to_string());
+    }
+    let mut segment_count = 0;
+    for segment in segments {
+        let index = segment
+            .strip_suffix('\'')
+            .ok_or_else(|| format!("Invalid derivation path - segment {} must be hardened for ed25519", segment))?;
+        index
+            .parse::<u32>()
+            .ok()
+            .filter(|value| *value < 0x8000_0000)
+            .ok_or_else(|| format!("Invalid derivation path - bad index {}", segment))?;
+        segment_count += 1;
+    }
+    if segment_count == 0 {
+        return Err("Invalid derivation path - at least one segment is required".to_string());
+    }
+    DerivationPath::from_absolute_path_str(trimmed_path)
+        .map_err(|error| format!("Invalid derivation path - {}", error))
+}
+
+pub fn derive_keypair(seed: &[u8], path: &str) -> Result<Keypair, String> {
+    let derivation_path = parse_derivation_path(path)?;
+    keypair_from_seed_and_derivation_path(seed, Some(derivation_path))
+        .map_err(|error| format!("Failed to derive keypair: {}", error))
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::Signer;
+
+    const ABANDON_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn hex_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn derive_keypair_matches_slip10_vector() {
+        let seed = hex_bytes("000102030405060708090a0b0c0d0e0f");
+        let derived_keypair = derive_keypair(&seed, "m/0'").unwrap();
+        assert_eq!(
+            derived_keypair.pubkey().to_bytes().to_vec(),
+            hex_bytes("8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c"),
+        );
+    }
+
+    #[test]
+    fn derive_keypair_matches_wallet_default_path() {
+        let seed = seed_from_mnemonic(ABANDON_MNEMONIC, "").unwrap();
+        let derived_keypair = derive_keypair(&seed, "m/44'/501'/0'/0'").unwrap();
+        assert_eq!(derived_keypair.pubkey().to_string(), "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk");
+    }
+
+    #[test]
+    fn seed_from_mnemonic_normalizes_whitespace_and_uses_passphrase() {
+        let spaced_phrase = format!("  {}  ", ABANDON_MNEMONIC.replace(' ', "   "));
+        assert_eq!(seed_from_mnemonic(&spaced_phrase, "").unwrap(), seed_from_mnemonic(ABANDON_MNEMONIC, "").unwrap());
+        assert_ne!(seed_from_mnemonic(ABANDON_MNEMONIC, "TREZOR").unwrap(), seed_from_mnemonic(ABANDON_MNEMONIC, "").unwrap());
+        assert!(seed_from_mnemonic("abandon abandon abandon", "").is_err());
+    }
+
+    #[test]
+    fn parse_derivation_path_requires_hardened_segments() {
+        assert!(parse_derivation_path("m/44'/501'/0'/0'").is_ok());
+        assert!(parse_derivation_path("m/44'/501'/0/0").is_err());
+        assert!(parse_derivation_path("44'/501'").is_err());
+        assert!(parse_derivation_path("m").is_err());
+        assert!(parse_derivation_path("m/2147483648'").is_err());
+    }
+
+    #[test]
+    fn generate_mnemonic_supports_12_and_24_words() {
+        assert_eq!(generate_mnemonic(12).unwrap().split_whitespace().count(), 12);
+        assert_eq!(generate_mnemonic(24).unwrap().split_whitespace().count(), 24);
+        assert!(generate_mnemonic(18).is_err());
+    }
+}
@@ -1,2 +1,25 @@
 pub mod crypto;
-pub mod validation;
\ No newline at end of file
+pub mod validation;
+
+use serde::{Deserialize, Deserializer};
+
+pub fn parse_amount_field<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AmountValue {
+        Number(u64),
+        Text(String),
+    }
+    match Option::<AmountValue>::deserialize(deserializer)? {
+        Some(AmountValue::Number(amount)) => Ok(Some(amount)),
+        Some(AmountValue::Text(text)) => text
+            .trim()
+            .parse::<u64>()
+            .map(Some)
+            .map_err(|_| serde::de::Error::custom("Invalid amount format")),
+        None => Ok(None),
+    }
+}
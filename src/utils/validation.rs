@@ -1,6 +1,37 @@
+use solana_sdk::hash::Hash;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction::MAX_PERMITTED_DATA_LENGTH;
+use spl_token_2022::instruction::AuthorityType;
 use std::str::FromStr;
 
+/// Largest memo that still fits in a single-signer transaction alongside a
+/// transfer; the memo program itself only bounds memos by transaction size.
+pub const MAX_MEMO_BYTES: usize = 566;
+
+/// Decimal places in a SOL amount, i.e. lamport precision.
+pub const SOL_DECIMALS: u8 = LAMPORTS_PER_SOL.ilog10() as u8;
+
+/// Request and inspection names for every SPL Token / Token-2022 authority
+/// type; both directions of the mapping go through this table.
+const AUTHORITY_TYPE_NAMES: [(AuthorityType, &str); 15] = [
+    (AuthorityType::MintTokens, "mint_tokens"),
+    (AuthorityType::FreezeAccount, "freeze_account"),
+    (AuthorityType::AccountOwner, "account_owner"),
+    (AuthorityType::CloseAccount, "close_account"),
+    (AuthorityType::TransferFeeConfig, "transfer_fee_config"),
+    (AuthorityType::WithheldWithdraw, "withheld_withdraw"),
+    (AuthorityType::CloseMint, "close_mint"),
+    (AuthorityType::InterestRate, "interest_rate"),
+    (AuthorityType::PermanentDelegate, "permanent_delegate"),
+    (AuthorityType::ConfidentialTransferMint, "confidential_transfer_mint"),
+    (AuthorityType::TransferHookProgramId, "transfer_hook_program_id"),
+    (AuthorityType::ConfidentialTransferFeeConfig, "confidential_transfer_fee_config"),
+    (AuthorityType::MetadataPointer, "metadata_pointer"),
+    (AuthorityType::GroupPointer, "group_pointer"),
+    (AuthorityType::GroupMemberPointer, "group_member_pointer"),
+];
+
 pub fn validate_pubkey_format(address_str: &str, field_identifier: &str) -> Result<Pubkey, String> {
     let trimmed_address = address_str.trim();
     if trimmed_address.len() < 32 || trimmed_address.len() > 44 {
@@ -10,6 +41,13 @@ pub fn validate_pubkey_format(address_str: &str, field_identifier: &str) -> Resu
         .map_err(|_| format!("Invalid {} address", field_identifier))
 }
 
+pub fn parse_optional_pubkey(address: Option<&str>, field_identifier: &str) -> Result<Option<Pubkey>, String> {
+    match address.map(str::trim) {
+        Some(addr) if !addr.is_empty() => validate_pubkey_format(addr, field_identifier).map(Some),
+        _ => Ok(None),
+    }
+}
+
 pub fn validate_amount_bounds(amount_value: u64, field_identifier: &str) -> Result<(), String> {
     if amount_value == 0 {
         return Err(format!("Invalid {} - amount must be greater than 0", field_identifier));
@@ -32,4 +70,139 @@ pub fn validate_message_constraints(message_content: &str) -> Result<(), String>
         return Err("Message too long - maximum 1024 characters".to_string());
     }
     Ok(())
-} 
\ No newline at end of file
+}
+
+pub fn validate_memo(memo: &str) -> Result<(), String> {
+    if memo.is_empty() {
+        return Err("Memo cannot be empty".to_string());
+    }
+    if memo.len() > MAX_MEMO_BYTES {
+        return Err(format!("Memo too long - maximum {} bytes", MAX_MEMO_BYTES));
+    }
+    Ok(())
+}
+
+pub fn validate_account_space(space: u64) -> Result<u64, String> {
+    if space > MAX_PERMITTED_DATA_LENGTH {
+        return Err(format!("Invalid space - maximum is {} bytes", MAX_PERMITTED_DATA_LENGTH));
+    }
+    Ok(space)
+}
+
+pub fn validate_blockhash_format(blockhash_str: &str) -> Result<Hash, String> {
+    Hash::from_str(blockhash_str.trim())
+        .map_err(|_| "Invalid recent blockhash".to_string())
+}
+
+pub fn validate_token_program(program_value: Option<&str>) -> Result<Pubkey, String> {
+    match program_value.map(str::trim) {
+        None | Some("") | Some("spl-token") => Ok(spl_token::ID),
+        Some("token-2022") | Some("spl-token-2022") => Ok(spl_token_2022::ID),
+        Some(program_address) => {
+            let parsed_program = validate_pubkey_format(program_address, "token program")?;
+            if parsed_program != spl_token::ID && parsed_program != spl_token_2022::ID {
+                return Err("Unsupported token program - expected spl-token or token-2022".to_string());
+            }
+            Ok(parsed_program)
+        },
+    }
+}
+
+pub fn parse_ui_amount(ui_amount: &str, decimals: u8) -> Result<u64, String> {
+    let trimmed_amount = ui_amount.trim();
+    let (whole_part, fraction_part) = match trimmed_amount.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (trimmed_amount, ""),
+    };
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (whole_part.is_empty() && fraction_part.is_empty()) || !is_digits(whole_part) || !is_digits(fraction_part) {
+        return Err("Invalid ui_amount format".to_string());
+    }
+    if fraction_part.len() > decimals as usize {
+        return Err(format!("Invalid ui_amount - at most {} decimal places allowed", decimals));
+    }
+    let scaled_digits = format!("{}{:0<width$}", whole_part, fraction_part, width = decimals as usize);
+    scaled_digits
+        .parse::<u64>()
+        .map_err(|_| "Invalid ui_amount - amount too large".to_string())
+}
+
+pub fn parse_authority_type(name: &str) -> Result<AuthorityType, String> {
+    AUTHORITY_TYPE_NAMES
+        .iter()
+        .find(|(_, type_name)| *type_name == name)
+        .map(|(authority_type, _)| authority_type.clone())
+        .ok_or_else(|| format!("Unsupported authority type: {}", name))
+}
+
+pub fn authority_type_name(authority_type: &AuthorityType) -> &'static str {
+    AUTHORITY_TYPE_NAMES
+        .iter()
+        .find(|(known_type, _)| known_type == authority_type)
+        .map(|(_, type_name)| *type_name)
+        .unwrap_or("unknown")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_optional_pubkey_treats_blank_as_absent() {
+        assert_eq!(parse_optional_pubkey(None, "owner"), Ok(None));
+        assert_eq!(parse_optional_pubkey(Some("  "), "owner"), Ok(None));
+        let address = Pubkey::new_unique();
+        assert_eq!(parse_optional_pubkey(Some(&format!(" {} ", address)), "owner"), Ok(Some(address)));
+        assert_eq!(parse_optional_pubkey(Some("not-a-key"), "owner"), Err("Invalid owner address format".to_string()));
+    }
+
+    #[test]
+    fn validate_memo_enforces_length_bounds() {
+        assert!(validate_memo("order 42").is_ok());
+        assert!(validate_memo(&"a".repeat(MAX_MEMO_BYTES)).is_ok());
+        assert!(validate_memo(&"a".repeat(MAX_MEMO_BYTES + 1)).is_err());
+        assert!(validate_memo("").is_err());
+    }
+
+    #[test]
+    fn validate_memo_counts_bytes_not_characters() {
+        let multibyte_memo = "é".repeat(MAX_MEMO_BYTES / 2 + 1);
+        assert!(multibyte_memo.chars().count() < MAX_MEMO_BYTES);
+        assert!(validate_memo(&multibyte_memo).is_err());
+    }
+
+    #[test]
+    fn parse_ui_amount_scales_by_decimals() {
+        assert_eq!(parse_ui_amount("1", 6), Ok(1_000_000));
+        assert_eq!(parse_ui_amount("1.5", 6), Ok(1_500_000));
+        assert_eq!(parse_ui_amount("0.000001", 6), Ok(1));
+        assert_eq!(parse_ui_amount(".25", 2), Ok(25));
+        assert_eq!(parse_ui_amount("3.", 2), Ok(300));
+        assert_eq!(parse_ui_amount(" 42 ", 0), Ok(42));
+    }
+
+    #[test]
+    fn parse_ui_amount_rejects_malformed_input() {
+        for ui_amount in ["", ".", "-1", "1e3", "1,5", "1.2.3", "abc"] {
+            assert!(parse_ui_amount(ui_amount, 6).is_err(), "{:?} should be rejected", ui_amount);
+        }
+    }
+
+    #[test]
+    fn parse_ui_amount_rejects_excess_precision_and_overflow() {
+        assert!(parse_ui_amount("1.0000001", 6).is_err());
+        assert!(parse_ui_amount("1.5", 0).is_err());
+        assert!(parse_ui_amount("18446744073709551616", 0).is_err());
+        assert!(parse_ui_amount("18446744073.709551616", 9).is_err());
+        assert_eq!(parse_ui_amount("18446744073.709551615", 9), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn authority_type_names_round_trip() {
+        for (authority_type, name) in AUTHORITY_TYPE_NAMES.iter() {
+            assert_eq!(parse_authority_type(name).as_ref(), Ok(authority_type));
+            assert_eq!(authority_type_name(authority_type), *name);
+        }
+        assert_eq!(parse_authority_type("MintTokens"), Err("Unsupported authority type: MintTokens".to_string()));
+    }
+}